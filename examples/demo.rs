@@ -20,6 +20,10 @@ fn polynomial() {
     let r = solvers::yuksel::roots_cubic(&p, tol);
     assert_eq!(r.len(), 3); // count roots
     assert_eq!(r, [-7.0, 0.0, 2.0]); // verify ordered roots
+
+    // Solvers implementing RootFinder can be swapped per call
+    let r = p.roots_with::<solvers::blinn::Blinn>(tol);
+    assert_eq!(r.len(), 3);
 }
 
 fn vector() {
@@ -0,0 +1,366 @@
+//! Polynomials in the Bernstein basis on [0, 1].
+//!
+//! A polynomial of degree n written as Σ b_i C(n, i) t^i (1 - t)^(n - i) has
+//! the b_i as the ordinates of the control points (i / n, b_i) of a Bézier
+//! curve, which is how curves are usually given. Its graph on [0, 1] lies in
+//! the convex hull of the control points, and de Casteljau's algorithm both
+//! evaluates it and splits it into the control points of its two halves, using
+//! only convex combinations, so it is evaluated about as stably as possible.
+//! Bézier clipping finds the roots by repeatedly cutting away the parts of the
+//! interval where the convex hull doesn't meet the axis.
+
+use crate::{float::Float, number::Number, polynomial::Polynomial, solvers::RootSet};
+
+/// Depth of the clipping search, beyond which it fails.
+const CLIP_STACK: usize = 64;
+
+/// Iteration limit for the clipping search.
+const CLIP_MAX_ITERATIONS: usize = 4096;
+
+/// A polynomial of degree N - 1 on [0, 1] in the Bernstein basis, given by the
+/// ordinates c of its control points.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BernsteinPolynomial<T: Number<Type = T>, const N: usize>
+where
+    T: Float,
+{
+    pub c: [T; N],
+}
+
+impl<T: Number<Type = T>, const N: usize> BernsteinPolynomial<T, N>
+where
+    T: Float,
+{
+    pub const fn new(c: [T; N]) -> Self {
+        Self { c }
+    }
+
+    /// Evaluates the polynomial at t by de Casteljau's algorithm.
+    pub fn eval(&self, t: T) -> T {
+        if N == 0 {
+            return T::ZERO;
+        }
+        let mut b = self.c;
+        for k in 1..N {
+            for i in 0..N - k {
+                b[i] = lerp(b[i], b[i + 1], t);
+            }
+        }
+        b[0]
+    }
+
+    /// Splits the polynomial at t into the control points of its restrictions
+    /// to [0, t] and [t, 1], each reparametrised over [0, 1].
+    pub fn subdivide(&self, t: T) -> (Self, Self) {
+        let mut left = self.c;
+        let mut right = self.c;
+        let mut b = self.c;
+        for k in 1..N {
+            for i in 0..N - k {
+                b[i] = lerp(b[i], b[i + 1], t);
+            }
+            left[k] = b[0];
+            right[N - 1 - k] = b[N - 1 - k];
+        }
+        (Self::new(left), Self::new(right))
+    }
+
+    /// The restriction of the polynomial to [a, b], reparametrised over
+    /// [0, 1].
+    pub fn restrict(&self, a: T, b: T) -> Self {
+        if b == T::ZERO {
+            return Self::new([self.c[0]; N]);
+        }
+        let (left, _) = self.subdivide(b);
+        left.subdivide(a / b).1
+    }
+
+    /// Returns the same polynomial of one degree higher, whose control points
+    /// lie closer to its graph.
+    pub fn elevate(&self) -> BernsteinPolynomial<T, { N + 1 }> {
+        // b'_i = i / (n + 1) b_(i-1) + (1 - i / (n + 1)) b_i
+        let mut c = [T::ZERO; N + 1];
+        let n = T::from_usize(N);
+        for (i, e) in c.iter_mut().enumerate() {
+            let a = T::from_usize(i) / n;
+            let lower = if i > 0 { self.c[i - 1] } else { T::ZERO };
+            let upper = if i < N { self.c[i] } else { T::ZERO };
+            *e = a * lower + (T::ONE - a) * upper;
+        }
+        BernsteinPolynomial::new(c)
+    }
+
+    /// Converts a monomial polynomial, by b_i = Σ_(k ≤ i) C(i, k) / C(n, k) a_k
+    /// over its ascending coefficients a_k.
+    pub fn from_monomial(p: &Polynomial<T, N>) -> Self {
+        let mut c = [T::ZERO; N];
+        let n = N.saturating_sub(1);
+        for (i, e) in c.iter_mut().enumerate() {
+            for k in 0..=i {
+                *e += binomial::<T>(i, k) / binomial::<T>(n, k) * p.c[N - 1 - k];
+            }
+        }
+        Self::new(c)
+    }
+
+    /// Converts the polynomial into the monomial basis, by
+    /// a_k = C(n, k) Σ_(i ≤ k) (-1)^(k - i) C(k, i) b_i.
+    pub fn to_monomial(&self) -> Polynomial<T, N> {
+        let mut c = [T::ZERO; N];
+        let n = N.saturating_sub(1);
+        for k in 0..N {
+            let mut a = T::ZERO;
+            for i in 0..=k {
+                let term = binomial::<T>(k, i) * self.c[i];
+                a += if (k - i) % 2 == 0 { term } else { -term };
+            }
+            c[N - 1 - k] = binomial::<T>(n, k) * a;
+        }
+        Polynomial::new(c)
+    }
+
+    /// Finds the roots on [0, 1] by Bézier clipping, to within tol.
+    ///
+    /// The interval is clipped to where the convex hull of the control points
+    /// meets the axis, converging quadratically onto a simple root, and is
+    /// bisected where clipping stalls. Intervals narrower than tol are taken
+    /// as roots, with their half width as the error, and adjacent ones are
+    /// merged, as happens about a multiple root. The set is marked failed for
+    /// the zero polynomial or if the search is exhausted. Requires a real
+    /// floating point type.
+    pub fn roots(&self, tol: T) -> RootSet<T, { N - 1 }> {
+        let mut output = RootSet::new();
+        if N == 0 {
+            return output;
+        }
+        if self.c.iter().all(|&b| b == T::ZERO) {
+            output.fail();
+            return output;
+        }
+        let two = T::ONE + T::ONE;
+        let five = T::from_usize(5);
+
+        // The last accepted interval, extended while the next is adjacent
+        let mut found = None;
+
+        // Intervals are popped left first, so roots are found in order
+        let mut stack = [(*self, T::ZERO, T::ONE); CLIP_STACK];
+        let mut top = 1;
+        let mut iterations = 0;
+        while top > 0 {
+            iterations += 1;
+            if iterations > CLIP_MAX_ITERATIONS {
+                output.fail();
+                break;
+            }
+            top -= 1;
+            let (f, a, b) = stack[top];
+            let Some((u, v)) = f.clip() else {
+                continue;
+            };
+            let (lo, hi) = (a + (b - a) * u, a + (b - a) * v);
+            if hi - lo <= tol {
+                accept(&mut found, lo, hi, tol, &mut output);
+                continue;
+            }
+
+            // Clips leaving more than 4/5 of the interval are followed by a
+            // bisection, as happens about a multiple root or several close ones
+            let f = f.restrict(u, v);
+            if five * (v - u) <= five - T::ONE {
+                stack[top] = (f, lo, hi);
+                top += 1;
+                continue;
+            }
+            let mid = (lo + hi) / two;
+            if mid <= lo || mid >= hi {
+                // The interval can't be split any further
+                accept(&mut found, lo, hi, tol, &mut output);
+                continue;
+            }
+            if top + 2 > CLIP_STACK {
+                output.fail();
+                continue;
+            }
+            let (left, right) = f.subdivide(T::ONE / two);
+            stack[top] = (right, mid, hi);
+            stack[top + 1] = (left, lo, mid);
+            top += 2;
+        }
+        if let Some((a, b)) = found {
+            push_root(&mut output, a, b);
+        }
+        output
+    }
+
+    /// The interval [u, v] of [0, 1] where the convex hull of the control
+    /// points meets the axis, or None where it doesn't.
+    fn clip(&self) -> Option<(T, T)> {
+        let n = T::from_usize(N - 1);
+        let x = |i: usize| if N > 1 { T::from_usize(i) / n } else { T::ZERO };
+        let mut u = T::INFINITY;
+        let mut v = T::NEG_INFINITY;
+        let mut extend = |t: T| {
+            u = if t < u { t } else { u };
+            v = if t > v { t } else { v };
+        };
+        for i in 0..N {
+            let bi = self.c[i];
+            if bi == T::ZERO {
+                extend(x(i));
+                continue;
+            }
+            for j in i + 1..N {
+                let bj = self.c[j];
+                if (bi < T::ZERO) != (bj < T::ZERO) && bj != T::ZERO {
+                    extend(x(i) + (x(j) - x(i)) * bi / (bi - bj));
+                }
+            }
+        }
+        if u > v {
+            return None;
+        }
+        let clamp = |t: T| {
+            if t < T::ZERO {
+                T::ZERO
+            } else if t > T::ONE {
+                T::ONE
+            } else {
+                t
+            }
+        };
+        Some((clamp(u), clamp(v)))
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize> From<Polynomial<T, N>> for BernsteinPolynomial<T, N>
+where
+    T: Float,
+{
+    fn from(p: Polynomial<T, N>) -> Self {
+        Self::from_monomial(&p)
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize> From<BernsteinPolynomial<T, N>> for Polynomial<T, N>
+where
+    T: Float,
+{
+    fn from(p: BernsteinPolynomial<T, N>) -> Self {
+        p.to_monomial()
+    }
+}
+
+#[inline]
+fn lerp<T: Float>(a: T, b: T, t: T) -> T {
+    a + (b - a) * t
+}
+
+/// The binomial coefficient C(n, k) for k ≤ n.
+fn binomial<T: Float>(n: usize, k: usize) -> T {
+    let k = k.min(n - k);
+    (0..k).fold(T::ONE, |c, i| {
+        c * T::from_usize(n - i) / T::from_usize(i + 1)
+    })
+}
+
+/// Accepts [lo, hi] as holding a root, merged with the last accepted interval
+/// when within tol of it, or else adding that one to the set.
+fn accept<T: Float, const CAP: usize>(
+    found: &mut Option<(T, T)>,
+    lo: T,
+    hi: T,
+    tol: T,
+    output: &mut RootSet<T, CAP>,
+) {
+    *found = match *found {
+        Some((a, b)) if lo - b <= tol => Some((a, hi)),
+        Some((a, b)) => {
+            push_root(output, a, b);
+            Some((lo, hi))
+        }
+        None => Some((lo, hi)),
+    };
+}
+
+/// Adds the root in [a, b] at its midpoint, failing the set once it is full.
+fn push_root<T: Float, const CAP: usize>(output: &mut RootSet<T, CAP>, a: T, b: T) {
+    let two = T::ONE + T::ONE;
+    if output.len() == CAP {
+        output.fail();
+    } else {
+        output.insert((a + b) / two, 1, Some((b - a) / two));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn de_casteljau() {
+        // (1 - t)^2 - t^2 = 1 - 2t
+        let b = BernsteinPolynomial::new([1., 0., -1.]);
+        assert_eq!(b.eval(0.), 1.);
+        assert_eq!(b.eval(0.25), 0.5);
+        assert_eq!(b.to_monomial(), Polynomial::new([0., -2., 1.]));
+
+        let b = BernsteinPolynomial::new([2., -1., 3., 0.5]);
+        let (l, r) = b.subdivide(0.3);
+        for t in [0., 0.2, 0.5, 1.] {
+            assert!((l.eval(t) - b.eval(0.3 * t)).abs() < 1e-15);
+            assert!((r.eval(t) - b.eval(0.3 + 0.7 * t)).abs() < 1e-15);
+        }
+        let s = b.restrict(0.2, 0.6);
+        for t in [0., 0.5, 1.] {
+            assert!((s.eval(t) - b.eval(0.2 + 0.4 * t)).abs() < 1e-15);
+        }
+
+        let e = b.elevate();
+        for t in [0., 0.1, 0.7, 1.] {
+            assert!((e.eval(t) - b.eval(t)).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn conversion() {
+        let p = Polynomial::new([0.5, -1., 2., 3., -4.]);
+        let b = BernsteinPolynomial::from(p);
+        let q: Polynomial<f64, 5> = b.into();
+        for (x, y) in q.c.iter().zip(p.c) {
+            assert!((x - y).abs() < 1e-14);
+        }
+        for t in [0., 0.3, 0.9, 1.] {
+            assert!((b.eval(t) - p.eval(t)).abs() < 1e-14);
+        }
+    }
+
+    #[test]
+    fn clipping() {
+        // (t - 0.1)(t - 0.45)(t - 0.5)(t - 2), with one root outside [0, 1]
+        let p = Polynomial::new([1., -0.1])
+            * Polynomial::new([1., -0.45])
+            * Polynomial::new([1., -0.5])
+            * Polynomial::new([1., -2.]);
+        let r = BernsteinPolynomial::from(p).roots(1e-12);
+        assert_eq!(r.len(), 3, "{:?}", r);
+        for (a, b) in r.iter().zip([0.1, 0.45, 0.5]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert!(r.error(0).unwrap() <= 1e-12);
+
+        // No roots, roots at both ends, and a double root
+        assert!(BernsteinPolynomial::new([1., -0.5, 2.])
+            .roots(1e-12)
+            .is_empty());
+        let r = BernsteinPolynomial::new([0., 1., 0.]).roots(1e-12);
+        assert_eq!(r, [0., 1.]);
+        let p = Polynomial::new([1., -0.25]) * Polynomial::new([1., -0.25]);
+        let r = BernsteinPolynomial::from(p).roots(1e-12);
+        assert_eq!(r.len(), 1, "{:?}", r);
+        assert!((r[0] - 0.25).abs() < 1e-7);
+
+        let r = BernsteinPolynomial::new([0.; 3]).roots(1e-12);
+        assert!(r.is_empty() && r.failed());
+    }
+}
@@ -99,6 +99,27 @@ where
         }
     }
 
+    /// Returns the modulus |z|, scaled to avoid intermediate overflow.
+    pub fn norm(&self) -> F {
+        let a = self.re.abs();
+        let b = self.im.abs();
+        let (max, min) = if a > b { (a, b) } else { (b, a) };
+        if max == F::ZERO {
+            F::ZERO
+        } else {
+            let r = min / max;
+            max * (F::ONE + r * r).sqrt()
+        }
+    }
+
+    /// Returns the complex conjugate.
+    pub fn conj(&self) -> Self {
+        Self {
+            re: self.re,
+            im: -self.im,
+        }
+    }
+
     pub fn powi(&self, power: i32) -> Self {
         let mut result = Self::ONE;
         for _ in 0..power {
@@ -818,6 +839,17 @@ mod tests {
         assert_eq!(d.powi(4), [3290588.0, -4317984.0].into());
     }
 
+    #[test]
+    fn test_c64_norm_conj() {
+        let a: c64 = [3.0, -4.0].into();
+        assert_eq!(a.norm(), 5.0);
+        assert_eq!(a.conj(), [3.0, 4.0].into());
+        assert_eq!(c64::ZERO.norm(), 0.0);
+
+        let b: c64 = [3e200, 4e200].into();
+        assert!((b.norm() - 5e200).abs() < 1e186);
+    }
+
     #[test]
     fn test_f32_sqrt() {
         let a: f32 = 2.0;
@@ -827,7 +859,7 @@ mod tests {
         assert_eq!(b.sqrt(), 0_f32.sqrt());
 
         let c: f32 = -2.0;
-        assert_eq!(c.sqrt().is_nan(), true);
+        assert!(c.sqrt().is_nan());
 
         let d: f32 = 1.0;
         assert_eq!(d.sqrt(), 1_f32.sqrt());
@@ -835,6 +867,8 @@ mod tests {
 
     #[test]
     fn test_c32_sqrt() {
+        use core::f32::consts::FRAC_1_SQRT_2;
+
         let a: c32 = [0.0, 0.0].into();
         assert_eq!(a.sqrt(), [0.0, 0.0].into());
 
@@ -842,12 +876,12 @@ mod tests {
         assert_eq!(b.sqrt(), [1.0, 0.0].into());
 
         let c: c32 = [0.0, 1.0].into();
-        assert_eq!(c.sqrt(), [0.7071067811865476, 0.7071067811865476].into());
+        assert_eq!(c.sqrt(), [FRAC_1_SQRT_2, FRAC_1_SQRT_2].into());
 
         let d: c32 = [1.0, 1.0].into();
-        assert_eq!(d.sqrt(), [1.09868411346781, 0.45508986056222733].into());
+        assert_eq!(d.sqrt(), [1.098_684_1, 0.455_089_87].into());
 
         let e: c32 = [1.0, -1.0].into();
-        assert_eq!(e.sqrt(), [1.09868411346781, -0.45508986056222733].into());
+        assert_eq!(e.sqrt(), [1.098_684_1, -0.455_089_87].into());
     }
 }
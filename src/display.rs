@@ -42,7 +42,7 @@ pub fn format_f64(n: f64, width: usize) -> String {
             output.push_str(&format!("{:.w$}", n_abs, w = width - formatting_width));
         } else {
             assert!(formatting_width <= width);
-            output.push_str(&format!("{:1.w$e}", n_abs, w = width - formatting_width));
+            output.push_str(&format!("{:.w$e}", n_abs, w = width - formatting_width));
         }
         width_used = width;
     } else if let FpCategory::Zero = class {
@@ -0,0 +1,361 @@
+//! Polynomials whose degree is known only at runtime, backed by the heap.
+//!
+//! `Polynomial<T, N>` fixes its degree at compile time, which suits embedded
+//! targets but not degrees read from a file. `DynPolynomial<T>` keeps its
+//! coefficients in a `Vec` trimmed of leading zeros, so its degree is always
+//! that of the polynomial, and converts to and from the fixed version.
+//! Requires the alloc feature.
+
+use alloc::vec::Vec;
+use core::ops::Index;
+
+use crate::{
+    float::Float,
+    number::Number,
+    polynomial::Polynomial,
+    solvers::{
+        blinn::Blinn,
+        multiset::{Heap, Multiset},
+        rootset::{ascending, keep_larger_error},
+        yuksel, RootSet,
+    },
+};
+
+/// A polynomial of any degree, with coefficients in descending order and no
+/// leading zeros, so that the zero polynomial has none.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynPolynomial<T: Number<Type = T>>
+where
+    T: Float,
+{
+    c: Vec<T>,
+}
+
+impl<T: Number<Type = T>> DynPolynomial<T>
+where
+    T: Float,
+{
+    /// The polynomial with the given descending coefficients, trimmed of
+    /// leading zeros.
+    pub fn new(mut c: Vec<T>) -> Self {
+        let s = c.iter().position(|&k| k != T::ZERO).unwrap_or(c.len());
+        c.drain(..s);
+        Self { c }
+    }
+
+    pub fn from_slice(c: &[T]) -> Self {
+        Self::new(c.to_vec())
+    }
+
+    /// The zero polynomial.
+    pub const fn zero() -> Self {
+        Self { c: Vec::new() }
+    }
+
+    /// The descending coefficients, starting from a nonzero leading one.
+    #[inline]
+    pub fn coefficients(&self) -> &[T] {
+        &self.c
+    }
+
+    /// The degree, where the zero polynomial is taken to have degree 0.
+    #[inline]
+    pub fn degree(&self) -> usize {
+        self.c.len().saturating_sub(1)
+    }
+
+    #[inline]
+    pub fn is_zero(&self) -> bool {
+        self.c.is_empty()
+    }
+
+    /// Evaluates the polynomial at x by Horner's scheme.
+    pub fn eval(&self, x: T) -> T {
+        self.c.iter().fold(T::ZERO, |acc, &k| acc * x + k)
+    }
+
+    /// Returns the derivative of the polynomial.
+    pub fn derivative(&self) -> Self {
+        let n = self.c.len();
+        let c = self.c[..n.saturating_sub(1)]
+            .iter()
+            .enumerate()
+            .map(|(i, &k)| k * T::from_usize(n - 1 - i))
+            .collect();
+        Self::new(c)
+    }
+
+    /// Returns the antiderivative with constant term k.
+    pub fn antiderivative(&self, k: T) -> Self {
+        let n = self.c.len();
+        let mut c: Vec<T> = self
+            .c
+            .iter()
+            .enumerate()
+            .map(|(i, &a)| a / T::from_usize(n - i))
+            .collect();
+        c.push(k);
+        Self::new(c)
+    }
+
+    /// Returns the definite integral from a to b.
+    pub fn integrate(&self, a: T, b: T) -> T {
+        let p = self.antiderivative(T::ZERO);
+        p.eval(b) - p.eval(a)
+    }
+
+    /// Finds the real roots, in closed form up to quadratics and by Yuksel's
+    /// method otherwise, as Polynomial::roots does.
+    pub fn roots(&self, tol: T) -> DynRootSet<T> {
+        let mut output = DynRootSet::new();
+        match self.degree() {
+            0 => {
+                // Every x is a root of the zero polynomial, which the set
+                // can't hold, while a nonzero constant has none
+                if self.is_zero() {
+                    output.fail();
+                }
+            }
+            1 => output.push(-self.c[1] / self.c[0]),
+            2 => output.merge(&Blinn::roots_quadratic_nopoly(
+                self.c[0], self.c[1], self.c[2],
+            )),
+            _ => {
+                for (x, m, e) in yuksel::roots_dyn(&self.c, tol) {
+                    output.insert(x, m, e);
+                }
+            }
+        }
+        output
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize> From<Polynomial<T, N>> for DynPolynomial<T>
+where
+    T: Float,
+{
+    fn from(p: Polynomial<T, N>) -> Self {
+        Self::from_slice(&p.c)
+    }
+}
+
+/// Converts into a fixed polynomial padded with leading zeros, failing with
+/// the original if its degree is too high.
+impl<T: Number<Type = T>, const N: usize> TryFrom<DynPolynomial<T>> for Polynomial<T, N>
+where
+    T: Float,
+{
+    type Error = DynPolynomial<T>;
+
+    fn try_from(p: DynPolynomial<T>) -> Result<Self, Self::Error> {
+        if p.c.len() > N {
+            return Err(p);
+        }
+        let mut c = [T::ZERO; N];
+        c[N - p.c.len()..].copy_from_slice(&p.c);
+        Ok(Polynomial::new(c))
+    }
+}
+
+/// The real roots of a DynPolynomial, as RootSet holds them for a
+/// Polynomial, but growing as needed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DynRootSet<T: Float> {
+    set: Multiset<T, Option<T>, Heap>,
+}
+
+impl<T: Float> DynRootSet<T> {
+    pub fn new() -> Self {
+        Self {
+            set: Multiset::new(T::ZERO, None),
+        }
+    }
+
+    /// Adds a simple root of unknown error.
+    #[inline]
+    pub fn push(&mut self, x: T) {
+        self.insert(x, 1, None);
+    }
+
+    /// Adds a root with its multiplicity and error bound. A root equal to one
+    /// already in the set adds to its multiplicity, keeping the larger error.
+    pub fn insert(&mut self, x: T, multiplicity: usize, error: Option<T>) {
+        if x.is_nan() {
+            self.set.fail();
+        } else if x.is_finite() {
+            self.set
+                .insert(x, multiplicity, error, ascending, keep_larger_error);
+        }
+    }
+
+    /// Adds every root of a fixed set, along with its failure.
+    pub fn merge<const CAP: usize>(&mut self, other: &RootSet<T, CAP>) {
+        for i in 0..other.len() {
+            self.insert(other[i], other.multiplicity(i), other.error(i));
+        }
+        if other.failed() {
+            self.fail();
+        }
+    }
+
+    /// Records that the solver broke down, so the set may be missing roots.
+    #[inline]
+    pub fn fail(&mut self) {
+        self.set.fail();
+    }
+
+    /// Whether the solver broke down, in which case the set may be missing
+    /// roots.
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.set.failed()
+    }
+
+    /// The number of distinct roots.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of roots counted with multiplicity.
+    pub fn count(&self) -> usize {
+        self.set.count()
+    }
+
+    /// The multiplicity of the ith root, or 0 past the end.
+    #[inline]
+    pub fn multiplicity(&self, i: usize) -> usize {
+        self.set.multiplicities().get(i).copied().unwrap_or(0)
+    }
+
+    /// The error bound on the ith root, if the solver provides one.
+    #[inline]
+    pub fn error(&self, i: usize) -> Option<T> {
+        self.set.payloads().get(i).copied().flatten()
+    }
+
+    /// The distinct roots in ascending order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.set.keys()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: Float> Default for DynRootSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, const CAP: usize> From<RootSet<T, CAP>> for DynRootSet<T> {
+    fn from(r: RootSet<T, CAP>) -> Self {
+        let mut output = Self::new();
+        output.merge(&r);
+        output
+    }
+}
+
+impl<T: Float> Index<usize> for DynRootSet<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.as_slice()[i]
+    }
+}
+
+/// Compares the distinct roots alone, in ascending order.
+impl<T: Float, const M: usize> PartialEq<[T; M]> for DynRootSet<T> {
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trimming() {
+        let p = DynPolynomial::new(vec![0., 0., 2., -1., 3.]);
+        assert_eq!(p.coefficients(), [2., -1., 3.]);
+        assert_eq!(p.degree(), 2);
+        assert_eq!(p.eval(2.), 9.);
+
+        let z = DynPolynomial::new(vec![0., 0.]);
+        assert!(z.is_zero());
+        assert_eq!(z, DynPolynomial::zero());
+        assert_eq!(z.eval(5.), 0.);
+
+        // The derivative of a linear polynomial is trimmed to a constant
+        let d = DynPolynomial::from_slice(&[3., 1.]).derivative();
+        assert_eq!(d.coefficients(), [3.]);
+        assert!(d.derivative().is_zero());
+    }
+
+    #[test]
+    fn calculus() {
+        let p = DynPolynomial::from_slice(&[3., 2., 1.]);
+        assert_eq!(p.derivative().coefficients(), [6., 2.]);
+        assert_eq!(p.antiderivative(4.).coefficients(), [1., 1., 1., 4.]);
+        assert_eq!(p.integrate(0., 1.), 3.);
+    }
+
+    #[test]
+    fn conversion() {
+        let p = Polynomial::new([0., 1., -3., 2.]);
+        let d = DynPolynomial::from(p);
+        assert_eq!(d.degree(), 2);
+        let q: Polynomial<f64, 4> = d.clone().try_into().unwrap();
+        assert_eq!(q, p);
+        let r: Result<Polynomial<f64, 2>, _> = d.try_into();
+        assert!(r.is_err());
+    }
+
+    #[test]
+    fn roots() {
+        let tol = 1e-12;
+
+        // Matches the fixed-degree roots, degree by degree
+        let x = DynPolynomial::from_slice(&[1., -3., -15., 19., 30.]);
+        let r = x.roots(tol);
+        assert_eq!(r.len(), 4);
+        for (a, b) in r.iter().zip([-3., -1., 2., 5.]) {
+            assert!((a - b).abs() < tol);
+        }
+        assert!(r.error(0).unwrap() < tol);
+
+        // A double root is returned once with its multiplicity
+        let r = DynPolynomial::from_slice(&[1., -5., 7., -3.]).roots(tol);
+        assert_eq!(r.len(), 2);
+        assert_eq!(r.multiplicity(0), 2);
+        assert_eq!(r.error(0), None);
+
+        let x = Polynomial::new([1., 5., -14., 0.]);
+        let a = DynPolynomial::from(x).roots(tol);
+        let b = x.roots(tol);
+        assert_eq!(a, DynRootSet::from(b));
+
+        assert_eq!(
+            DynPolynomial::from_slice(&[1., -2., 1.]).roots(tol).count(),
+            2
+        );
+        assert_eq!(DynPolynomial::from_slice(&[2., -1.]).roots(tol), [0.5]);
+        assert!(DynPolynomial::from_slice(&[1., 0., 1.])
+            .roots(tol)
+            .is_empty());
+        assert!(DynPolynomial::from_slice(&[3.]).roots(tol).is_empty());
+        let r = DynPolynomial::from_slice(&[1e-14]).roots(tol);
+        assert!(r.is_empty() && !r.failed());
+        assert!(DynPolynomial::<f64>::zero().roots(tol).failed());
+    }
+}
@@ -0,0 +1,271 @@
+//! Least-squares polynomial fitting.
+//!
+//! Fitting in the monomial basis solves a Vandermonde system, whose condition
+//! number grows exponentially with the degree, so a fit of degree 10 or so can
+//! lose every digit. Here the samples are mapped onto [-1, 1] and fitted in the
+//! Chebyshev basis, whose design matrix stays well conditioned, by a QR
+//! decomposition built one sample at a time with Givens rotations. This needs
+//! no storage beyond the N × N triangular factor, whatever the number of
+//! samples, and leaves the residual of the fit over once the rows are consumed.
+
+use crate::{
+    float::Float,
+    orthogonal::{Chebyshev, OrthogonalPolynomial},
+    polynomial::Polynomial,
+};
+
+/// A least-squares fit, with the norm of its (weighted) residual and the
+/// largest deviation from any sample.
+///
+/// The fitted polynomial is kept in the Chebyshev basis on the interval of
+/// the samples, which `eval` uses, as well as converted to the monomial basis,
+/// which loses accuracy for high degrees or samples far from the origin.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Fit<T: Float, const N: usize> {
+    pub polynomial: Polynomial<T, N>,
+    series: OrthogonalPolynomial<T, Chebyshev, N>,
+    scale: T,
+    offset: T,
+    /// The weighted residual norm √(Σ w_i (p(x_i) - y_i)²).
+    pub residual: T,
+    /// The largest unweighted residual |p(x_i) - y_i| over samples of nonzero
+    /// weight.
+    pub max_residual: T,
+}
+
+impl<T: Float, const N: usize> Fit<T, N> {
+    /// Evaluates the fit at x in the Chebyshev basis, which stays accurate
+    /// where the monomial polynomial doesn't.
+    #[inline]
+    pub fn eval(&self, x: T) -> T {
+        self.series.eval(self.scale * x + self.offset)
+    }
+}
+
+/// Fits a polynomial of degree N - 1 to the samples (xs, ys) by least
+/// squares. Returns None unless there are at least N distinct abscissae.
+/// Requires a real floating point type.
+///
+/// # Panics
+///
+/// If xs and ys differ in length.
+pub fn fit<T: Float, const N: usize>(xs: &[T], ys: &[T]) -> Option<Fit<T, N>> {
+    assert_eq!(xs.len(), ys.len(), "mismatched samples");
+    solve(xs, ys, |_| T::ONE)
+}
+
+/// Fits a polynomial of degree N - 1 to the samples (xs, ys), minimising the
+/// sum of squared residuals each scaled by its weight, as by the inverse
+/// variance of the sample. Samples of zero weight are ignored. Returns None
+/// for a negative or non-finite weight, or unless there are at least N
+/// distinct abscissae of nonzero weight.
+///
+/// # Panics
+///
+/// If xs, ys and ws differ in length.
+pub fn fit_weighted<T: Float, const N: usize>(xs: &[T], ys: &[T], ws: &[T]) -> Option<Fit<T, N>> {
+    assert_eq!(xs.len(), ys.len(), "mismatched samples");
+    assert_eq!(xs.len(), ws.len(), "mismatched weights");
+    if ws.iter().any(|&w| !w.is_finite() || w < T::ZERO) {
+        return None;
+    }
+    solve(xs, ys, |i| ws[i])
+}
+
+fn solve<T: Float, const N: usize>(
+    xs: &[T],
+    ys: &[T],
+    weight: impl Fn(usize) -> T,
+) -> Option<Fit<T, N>> {
+    if N == 0 {
+        return None;
+    }
+    let two = T::ONE + T::ONE;
+
+    // The interval spanned by the weighted samples, mapped onto [-1, 1] by
+    // t = s x + o
+    let mut lo = T::INFINITY;
+    let mut hi = T::NEG_INFINITY;
+    for (i, &x) in xs.iter().enumerate() {
+        if weight(i) > T::ZERO {
+            lo = if x < lo { x } else { lo };
+            hi = if x > hi { x } else { hi };
+        }
+    }
+    if !lo.is_finite() || !hi.is_finite() {
+        return None;
+    }
+    let (s, o) = if hi > lo {
+        (two / (hi - lo), -(hi + lo) / (hi - lo))
+    } else {
+        (T::ZERO, T::ZERO)
+    };
+
+    // R c = z, with each sample rotated into the triangle in turn
+    let mut r = [[T::ZERO; N]; N];
+    let mut z = [T::ZERO; N];
+    let mut sum_squares = T::ZERO;
+    for (i, (&x, &y)) in xs.iter().zip(ys).enumerate() {
+        let w = weight(i);
+        if w == T::ZERO {
+            continue;
+        }
+        let w = w.sqrt();
+        let mut row = chebyshev_row::<T, N>(s * x + o);
+        for e in row.iter_mut() {
+            *e *= w;
+        }
+        let mut rhs = w * y;
+        for k in 0..N {
+            if row[k] == T::ZERO {
+                continue;
+            }
+            let (c, sn) = givens(r[k][k], row[k]);
+            for j in k..N {
+                let (a, b) = (r[k][j], row[j]);
+                r[k][j] = c * a + sn * b;
+                row[j] = c * b - sn * a;
+            }
+            let a = z[k];
+            z[k] = c * a + sn * rhs;
+            rhs = c * rhs - sn * a;
+        }
+        sum_squares += rhs * rhs;
+    }
+
+    // Fewer distinct abscissae than coefficients leave the triangle singular
+    let max = r.iter().enumerate().fold(
+        T::ZERO,
+        |m, (k, row)| if row[k].abs() > m { row[k].abs() } else { m },
+    );
+    let floor = T::from_usize(N) * T::EPSILON * max;
+    if r.iter().enumerate().any(|(k, row)| row[k].abs() <= floor) {
+        return None;
+    }
+
+    let mut c = [T::ZERO; N];
+    for k in (0..N).rev() {
+        let mut acc = z[k];
+        for j in k + 1..N {
+            acc -= r[k][j] * c[j];
+        }
+        c[k] = acc / r[k][k];
+    }
+
+    let series = OrthogonalPolynomial::<T, Chebyshev, N>::new(c);
+    let mut output = Fit {
+        polynomial: substitute(&series.to_monomial(), s, o),
+        series,
+        scale: s,
+        offset: o,
+        residual: sum_squares.sqrt(),
+        max_residual: T::ZERO,
+    };
+    for (i, (&x, &y)) in xs.iter().zip(ys).enumerate() {
+        let e = (output.eval(x) - y).abs();
+        if weight(i) > T::ZERO && e > output.max_residual {
+            output.max_residual = e;
+        }
+    }
+    Some(output)
+}
+
+/// The values of T_0(t) to T_(N-1)(t).
+fn chebyshev_row<T: Float, const N: usize>(t: T) -> [T; N] {
+    let two = T::ONE + T::ONE;
+    let mut row = [T::ZERO; N];
+    row[0] = T::ONE;
+    if N > 1 {
+        row[1] = t;
+    }
+    for k in 2..N {
+        row[k] = two * t * row[k - 1] - row[k - 2];
+    }
+    row
+}
+
+/// The rotation (c, s) taking (a, b) onto (√(a² + b²), 0), scaled against
+/// overflow.
+fn givens<T: Float>(a: T, b: T) -> (T, T) {
+    let m = if a.abs() > b.abs() { a.abs() } else { b.abs() };
+    let (a, b) = (a / m, b / m);
+    let h = (a * a + b * b).sqrt();
+    (a / h, b / h)
+}
+
+/// Returns p(s x + o).
+fn substitute<T: Float, const N: usize>(p: &Polynomial<T, N>, s: T, o: T) -> Polynomial<T, N> {
+    // Horner's scheme over polynomials, accumulated in ascending order
+    let mut acc = [T::ZERO; N];
+    for &k in p.c.iter() {
+        for i in (0..N).rev() {
+            let lower = if i > 0 { acc[i - 1] } else { T::ZERO };
+            acc[i] = o * acc[i] + s * lower;
+        }
+        acc[0] += k;
+    }
+    acc.reverse();
+    Polynomial::new(acc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exact() {
+        // Samples of a cubic are fitted exactly
+        let p = Polynomial::new([0.5, -2., 3., 7.]);
+        let xs = [-3., -1., 0., 0.5, 2., 4., 5.5];
+        let ys = xs.map(|x| p.eval(x));
+        let f = fit::<f64, 4>(&xs, &ys).unwrap();
+        for (a, b) in f.polynomial.c.iter().zip(p.c) {
+            assert!((a - b).abs() < 1e-12, "{:?}", f);
+        }
+        assert!(f.residual < 1e-12 && f.max_residual < 1e-12);
+
+        // And a line through two points
+        let f = fit::<f64, 2>(&[1., 3.], &[2., 6.]).unwrap();
+        assert!((f.polynomial.c[0] - 2.).abs() < 1e-15 && f.polynomial.c[1].abs() < 1e-15);
+    }
+
+    #[test]
+    fn least_squares() {
+        // The best line through (0, 0), (1, 1), (2, 1) and (3, 3) is
+        // 0.9x - 0.1, leaving residuals 0.1, 0.2, -0.7 and 0.4
+        let xs = [0., 1., 2., 3.];
+        let ys = [0., 1., 1., 3.];
+        let f = fit::<f64, 2>(&xs, &ys).unwrap();
+        assert!((f.polynomial.c[0] - 0.9).abs() < 1e-14);
+        assert!((f.polynomial.c[1] + 0.1).abs() < 1e-14);
+        assert!((f.residual - f64::sqrt(0.7)).abs() < 1e-14);
+        assert!((f.max_residual - 0.7).abs() < 1e-14);
+
+        // Weighting a sample by zero drops it, and heavily pulls the fit
+        // through it
+        let f = fit_weighted::<f64, 2>(&xs, &ys, &[1., 1., 0., 1.]).unwrap();
+        assert!((f.polynomial.c[0] - 1.).abs() < 1e-14 && f.polynomial.c[1].abs() < 1e-14);
+        let f = fit_weighted::<f64, 2>(&xs, &ys, &[1., 1., 1e12, 1.]).unwrap();
+        assert!((f.polynomial.eval(2.) - 1.).abs() < 1e-9);
+        assert!(fit_weighted::<f64, 2>(&xs, &ys, &[1., -1., 1., 1.]).is_none());
+    }
+
+    #[test]
+    fn degenerate() {
+        assert!(fit::<f64, 3>(&[1., 2., 1., 2.], &[0., 1., 0., 1.]).is_none());
+        assert!(fit::<f64, 2>(&[], &[]).is_none());
+        let f = fit::<f64, 1>(&[2., 2.], &[1., 3.]).unwrap();
+        assert_eq!(f.polynomial.c, [2.]);
+    }
+
+    #[test]
+    fn conditioning() {
+        // A degree 12 fit to sensor-like data far from the origin, where the
+        // Vandermonde matrix is hopelessly ill-conditioned
+        let xs: Vec<f64> = (0..=200).map(|i| 100. + i as f64 * 0.01).collect();
+        let ys: Vec<f64> = xs.iter().map(|x| f64::sin(x - 100.)).collect();
+        let f = fit::<f64, 13>(&xs, &ys).unwrap();
+        assert!(f.residual < 1e-12 && f.max_residual < 1e-13, "{:?}", f);
+        assert!((f.eval(101.234) - f64::sin(1.234)).abs() < 1e-13);
+    }
+}
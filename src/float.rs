@@ -48,6 +48,20 @@ pub trait Float: Natural + Field {
     fn copysign(self, sign: Self) -> Self;
     fn is_nan(self) -> bool;
     fn is_finite(self) -> bool;
+
+    /// Converts an unsigned integer to Self by binary expansion, which is exact
+    /// for any value representable in the mantissa.
+    fn from_usize(n: usize) -> Self {
+        let two = Self::ONE + Self::ONE;
+        let mut output = Self::ZERO;
+        for bit in (0..usize::BITS).rev() {
+            output *= two;
+            if (n >> bit) & 1 == 1 {
+                output += Self::ONE;
+            }
+        }
+        output
+    }
 }
 
 macro_rules! stack_float{
@@ -118,6 +132,10 @@ macro_rules! stack_float{
                 fn is_finite(self) -> bool {
                     <$basis>::is_finite(self)
                 }
+
+                fn from_usize(n: usize) -> Self {
+                    n as Self
+                }
             }
         )+
     };
@@ -161,5 +179,7 @@ mod tests {
 
         let b: f32 = 256.0;
         test_sqrt(b, 16.0);
+
+        assert_eq!(f64::from_usize(12), 12.0);
     }
 }
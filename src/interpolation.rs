@@ -0,0 +1,224 @@
+//! Polynomial interpolation through a set of points.
+//!
+//! There is exactly one polynomial of degree N - 1 through N points with
+//! distinct abscissae. Lagrange's and Newton's forms both build its monomial
+//! coefficients, Newton's by divided differences in O(N²), which is the
+//! better conditioned of the two. The barycentric form evaluates the same
+//! polynomial straight from the points in O(N), without ever forming its
+//! coefficients, and is stable for any choice of points. At Chebyshev nodes,
+//! which cluster towards the ends of the interval, the interpolant converges
+//! for any smooth function, where equispaced points diverge near the ends as
+//! the degree grows.
+
+use crate::{float::Float, number::Number, polynomial::Polynomial};
+
+/// The N Chebyshev nodes of the first kind on [a, b] in ascending order, the
+/// images of the roots of T_N.
+pub fn chebyshev_nodes<T: Float, const N: usize>(a: T, b: T) -> [T; N] {
+    let two = T::ONE + T::ONE;
+    let pi = T::ZERO.atan2(-T::ONE);
+    let mut output = [T::ZERO; N];
+    for (k, e) in output.iter_mut().enumerate() {
+        // cos((2k + 1)π / 2N), negated so the nodes ascend
+        let t = -(T::from_usize(2 * k + 1) * pi / T::from_usize(2 * N)).cos();
+        *e = (a + b) / two + (b - a) / two * t;
+    }
+    output
+}
+
+/// Whether the abscissae are all distinct.
+fn distinct<T: Float>(xs: &[T]) -> bool {
+    xs.iter()
+        .enumerate()
+        .all(|(i, &x)| xs[i + 1..].iter().all(|&y| x != y))
+}
+
+/// The polynomial through the points (xs, ys) as a sum of Lagrange basis
+/// polynomials, each the product of (x - x_j) / (x_i - x_j) over j ≠ i.
+/// Returns None if any abscissae repeat.
+pub fn lagrange<T: Float, const N: usize>(xs: &[T; N], ys: &[T; N]) -> Option<Polynomial<T, N>> {
+    if !distinct(xs) {
+        return None;
+    }
+    let mut output = [T::ZERO; N];
+    for i in 0..N {
+        // The basis polynomial in ascending order, scaled by y_i
+        let mut l = [T::ZERO; N];
+        l[0] = ys[i];
+        for (degree, j) in (0..N).filter(|&j| j != i).enumerate() {
+            let d = xs[i] - xs[j];
+            for k in (0..=degree + 1).rev() {
+                let lower = if k > 0 { l[k - 1] } else { T::ZERO };
+                l[k] = (lower - xs[j] * l[k]) / d;
+            }
+        }
+        for (e, k) in output.iter_mut().zip(l.iter().rev()) {
+            *e += *k;
+        }
+    }
+    Some(Polynomial::new(output))
+}
+
+/// The divided differences [x_0], [x_0, x_1], ..., [x_0, ..., x_(N-1)] of the
+/// points, the coefficients of the Newton form
+/// c_0 + c_1 (x - x_0) + c_2 (x - x_0)(x - x_1) + ...
+/// Returns None if any abscissae repeat.
+pub fn divided_differences<T: Float, const N: usize>(xs: &[T; N], ys: &[T; N]) -> Option<[T; N]> {
+    if !distinct(xs) {
+        return None;
+    }
+    let mut c = *ys;
+    for k in 1..N {
+        for i in (k..N).rev() {
+            c[i] = (c[i] - c[i - 1]) / (xs[i] - xs[i - k]);
+        }
+    }
+    Some(c)
+}
+
+/// The polynomial through the points (xs, ys), from its Newton form by
+/// divided differences. Returns None if any abscissae repeat.
+pub fn newton<T: Float, const N: usize>(xs: &[T; N], ys: &[T; N]) -> Option<Polynomial<T, N>> {
+    let c = divided_differences(xs, ys)?;
+
+    // Horner's scheme over the nested form, accumulated in ascending order
+    let mut acc = [T::ZERO; N];
+    for k in (0..N).rev() {
+        for i in (0..N).rev() {
+            let lower = if i > 0 { acc[i - 1] } else { T::ZERO };
+            acc[i] = lower - xs[k] * acc[i];
+        }
+        acc[0] += c[k];
+    }
+    acc.reverse();
+    Some(Polynomial::new(acc))
+}
+
+/// The polynomial through N points in barycentric form, evaluated in O(N)
+/// without forming its coefficients.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Barycentric<T: Number<Type = T>, const N: usize>
+where
+    T: Float,
+{
+    xs: [T; N],
+    ys: [T; N],
+    weights: [T; N],
+}
+
+impl<T: Number<Type = T>, const N: usize> Barycentric<T, N>
+where
+    T: Float,
+{
+    /// The interpolant through the points (xs, ys), with the weights
+    /// w_i = 1 / Π (x_i - x_j) over j ≠ i. Returns None if any abscissae
+    /// repeat.
+    pub fn new(xs: [T; N], ys: [T; N]) -> Option<Self> {
+        if !distinct(&xs) {
+            return None;
+        }
+        let mut weights = [T::ONE; N];
+        for (i, w) in weights.iter_mut().enumerate() {
+            for j in (0..N).filter(|&j| j != i) {
+                *w /= xs[i] - xs[j];
+            }
+        }
+        Some(Self { xs, ys, weights })
+    }
+
+    /// The interpolant of f at the N Chebyshev nodes on [a, b].
+    pub fn chebyshev(a: T, b: T, f: impl Fn(T) -> T) -> Option<Self> {
+        let xs = chebyshev_nodes::<T, N>(a, b);
+        Self::new(xs, xs.map(f))
+    }
+
+    /// The interpolation points.
+    #[inline]
+    pub fn points(&self) -> impl Iterator<Item = (T, T)> + '_ {
+        self.xs.iter().copied().zip(self.ys.iter().copied())
+    }
+
+    /// Evaluates the interpolant at x by the second barycentric formula,
+    /// Σ w_i y_i / (x - x_i) / Σ w_i / (x - x_i), which is exact at the
+    /// points.
+    pub fn eval(&self, x: T) -> T {
+        let mut num = T::ZERO;
+        let mut den = T::ZERO;
+        for i in 0..N {
+            let d = x - self.xs[i];
+            if d == T::ZERO {
+                return self.ys[i];
+            }
+            let t = self.weights[i] / d;
+            num += t * self.ys[i];
+            den += t;
+        }
+        num / den
+    }
+
+    /// Forms the monomial coefficients of the interpolant, for use with the
+    /// rest of the crate.
+    pub fn to_polynomial(&self) -> Polynomial<T, N> {
+        // The abscissae were checked to be distinct
+        newton(&self.xs, &self.ys).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constructors() {
+        // 2x^3 - x + 5
+        let p = Polynomial::new([2., 0., -1., 5.]);
+        let xs = [-1., 0.5, 2., 3.];
+        let ys = xs.map(|x| p.eval(x));
+        for q in [lagrange(&xs, &ys).unwrap(), newton(&xs, &ys).unwrap()] {
+            for (a, b) in q.c.iter().zip(p.c) {
+                assert!((a - b).abs() < 1e-13, "{:?}", q);
+            }
+        }
+        assert_eq!(divided_differences(&[0., 1.], &[1., 3.]), Some([1., 2.]));
+        assert!(lagrange(&[1., 2., 1.], &[0., 0., 0.]).is_none());
+        assert!(newton(&[1., 1.], &[0., 1.]).is_none());
+        assert_eq!(newton(&[4.], &[7.]), Some(Polynomial::new([7.])));
+
+        // The interpolant feeds the root finders
+        let r = newton(&[-1., 0., 2.], &[0., -2., 0.]).unwrap().roots(1e-12);
+        assert_eq!(r, [-1., 2.]);
+    }
+
+    #[test]
+    fn barycentric() {
+        let xs = [-1., 0.5, 2., 3.];
+        let ys = [1., -2., 0., 4.];
+        let b = Barycentric::new(xs, ys).unwrap();
+        let p = b.to_polynomial();
+        for (x, y) in b.points() {
+            assert_eq!(b.eval(x), y);
+        }
+        for x in [-2., 0., 1.3, 2.9] {
+            assert!((b.eval(x) - p.eval(x)).abs() < 1e-13);
+        }
+        assert!(Barycentric::new([0., 0.], [1., 2.]).is_none());
+    }
+
+    #[test]
+    fn chebyshev() {
+        let n = chebyshev_nodes::<f64, 5>(-1., 1.);
+        assert!(n.windows(2).all(|w| w[0] < w[1]));
+        assert!(n[2].abs() < 1e-16);
+        assert!((n[4] - f64::cos(std::f64::consts::PI / 10.)).abs() < 1e-15);
+
+        // Runge's function, which equispaced points fail to interpolate
+        let runge = |x: f64| 1. / (1. + 25. * x * x);
+        let b = Barycentric::<f64, 41>::chebyshev(-1., 1., runge).unwrap();
+        for x in [-0.97, -0.5, 0.1, 0.93] {
+            assert!((b.eval(x) - runge(x)).abs() < 1e-3);
+        }
+        let xs: [f64; 41] = core::array::from_fn(|i| -1. + i as f64 / 20.);
+        let e = Barycentric::new(xs, xs.map(runge)).unwrap();
+        assert!((e.eval(-0.97) - runge(-0.97)).abs() > 1.);
+    }
+}
@@ -8,26 +8,31 @@
 // }
 // #[cfg(not(debug_assertions))]
 // extern crate panic_semihosting;
-#![feature(associated_type_bounds)]
 #![feature(generic_const_exprs)]
-#![feature(generic_arg_infer)]
-#![feature(more_qualified_paths)]
-#![feature(const_float_bits_conv)]
 // #![feature(negative_impls)]
 #![feature(min_specialization)]
 #![allow(incomplete_features)]
-#![allow(soft_unstable)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
 
 pub mod algebra;
+pub mod bernstein;
 pub mod complex;
 mod display;
+#[cfg(feature = "alloc")]
+pub mod dynamic;
 pub mod element;
+pub mod fit;
 pub mod float;
 pub mod integer;
+pub mod interpolation;
 // pub mod lattice;
 pub mod matrix;
 pub mod natural;
 pub mod number;
+pub mod orthogonal;
 pub mod polynomial;
+pub mod rational;
 pub mod solvers;
 pub mod vector;
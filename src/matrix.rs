@@ -1,6 +1,9 @@
 use std::ops::{Add, Mul};
 
-use crate::{float::Float, number::Number};
+use crate::{complex::Complex, float::Float, number::Number};
+
+/// Iteration limit for the QR algorithm, per eigenvalue, as in EISPACK.
+const QR_MAX_ITERATIONS: usize = 30;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Matrix<T: Number<Type = T>, const M: usize, const N: usize>
@@ -67,9 +70,369 @@ where
         //     let ma4 = self.e[0][1].mul_add(ma2, -m4);
         //     self.e[0][0].mul_add(ma3, -ma4)
         } else {
-            todo!()
+            assert_eq!(M, N);
+            let mut e = self.e;
+            bareiss(&mut e, N)
+        }
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize> Matrix<T, N, N>
+where
+    T: Float,
+{
+    /// Reduces the matrix to upper Hessenberg form, zero below the first
+    /// subdiagonal, by a similarity transform of Householder reflections,
+    /// which leaves the eigenvalues unchanged.
+    pub fn hessenberg(&self) -> Self {
+        let mut e = self.e;
+        hessenberg(&mut e, N);
+        Self { e }
+    }
+
+    /// Finds every eigenvalue, real and complex, by balancing the matrix,
+    /// reducing it to Hessenberg form and running the shifted QR algorithm.
+    /// The eigenvalues are sorted by real and then imaginary part, with
+    /// complex conjugate pairs adjacent. Returns None if the iteration fails
+    /// to converge.
+    pub fn eigenvalues(&self) -> Option<[Complex<T>; N]> {
+        let mut e = self.e;
+        let mut output = [Complex::new(T::ZERO, T::ZERO); N];
+        if !eigenvalues(&mut e, N, &mut output) {
+            return None;
+        }
+        output.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+        Some(output)
+    }
+}
+
+/// The determinant of the leading n by n block of a by Bareiss' fraction-free
+/// elimination, destroying it. For a matrix of integers every division is
+/// exact, so the determinant is found exactly as long as each product of two
+/// minors fits in the mantissa, below 2^53 for f64 and 2^24 for f32. Past that,
+/// or for entries that aren't integers, it rounds as any elimination does,
+/// with no bound on the error. Pivots are the largest in their column.
+pub(crate) fn bareiss<T: Float, const M: usize>(a: &mut [[T; M]], n: usize) -> T {
+    if n == 0 {
+        return T::ONE;
+    }
+    let mut sign = T::ONE;
+    let mut prev = T::ONE;
+    for k in 0..n {
+        let p = (k..n).fold(k, |p, i| if a[i][k].abs() > a[p][k].abs() { i } else { p });
+        if a[p][k] == T::ZERO {
+            return T::ZERO;
+        }
+        if p != k {
+            a.swap(p, k);
+            sign = -sign;
+        }
+        let (upper, lower) = a.split_at_mut(k + 1);
+        let pivot = &upper[k];
+        for row in lower[..n - k - 1].iter_mut() {
+            for j in k + 1..n {
+                row[j] = (row[j] * pivot[k] - row[k] * pivot[j]) / prev;
+            }
+        }
+        prev = pivot[k];
+    }
+    sign * a[n - 1][n - 1]
+}
+
+/// Balances the leading n by n block of a, scaling rows and columns by powers
+/// of two until their norms are comparable, which reduces the rounding error
+/// of finding its eigenvalues without introducing any. Follows Parlett and
+/// Reinsch.
+pub(crate) fn balance<T: Float, const M: usize>(a: &mut [[T; M]], n: usize) {
+    let radix = T::ONE + T::ONE;
+    let square = radix * radix;
+    let threshold = T::from_usize(19) / T::from_usize(20);
+    let mut done = false;
+    while !done {
+        done = true;
+        for i in 0..n {
+            let mut c = T::ZERO;
+            let mut r = T::ZERO;
+            for j in (0..n).filter(|&j| j != i) {
+                c += a[j][i].abs();
+                r += a[i][j].abs();
+            }
+            if c == T::ZERO || r == T::ZERO || !(c + r).is_finite() {
+                continue;
+            }
+            let s = c + r;
+            let mut f = T::ONE;
+            let g = r / radix;
+            while c < g {
+                f *= radix;
+                c *= square;
+            }
+            let g = r * radix;
+            while c > g {
+                f /= radix;
+                c /= square;
+            }
+            if (c + r) / f < threshold * s {
+                done = false;
+                let g = f.recip();
+                for e in a[i][..n].iter_mut() {
+                    *e *= g;
+                }
+                for row in a[..n].iter_mut() {
+                    row[i] *= f;
+                }
+            }
+        }
+    }
+}
+
+/// Reduces the leading n by n block of a to upper Hessenberg form in place,
+/// by Householder reflections.
+#[allow(clippy::needless_range_loop)]
+pub(crate) fn hessenberg<T: Float, const M: usize>(a: &mut [[T; M]], n: usize) {
+    let two = T::ONE + T::ONE;
+    let mut v = [T::ZERO; M];
+    for k in 0..n.saturating_sub(2) {
+        let mut alpha = T::ZERO;
+        for row in a[k + 1..n].iter() {
+            alpha += row[k] * row[k];
+        }
+        alpha = alpha.sqrt();
+        if alpha == T::ZERO {
+            continue;
+        }
+        if a[k + 1][k] > T::ZERO {
+            alpha = -alpha;
+        }
+
+        // The reflection I - 2vv'/v'v maps column k below the diagonal onto
+        // alpha times the first unit vector
+        let mut norm = T::ZERO;
+        for i in k + 1..n {
+            v[i] = a[i][k];
+            if i == k + 1 {
+                v[i] -= alpha;
+            }
+            norm += v[i] * v[i];
+        }
+        if norm == T::ZERO {
+            continue;
+        }
+        for j in 0..n {
+            let mut dot = T::ZERO;
+            for i in k + 1..n {
+                dot += v[i] * a[i][j];
+            }
+            let f = two * dot / norm;
+            for i in k + 1..n {
+                a[i][j] -= f * v[i];
+            }
+        }
+        for row in a[..n].iter_mut() {
+            let mut dot = T::ZERO;
+            for j in k + 1..n {
+                dot += row[j] * v[j];
+            }
+            let f = two * dot / norm;
+            for j in k + 1..n {
+                row[j] -= f * v[j];
+            }
+        }
+        a[k + 1][k] = alpha;
+        for row in a[k + 2..n].iter_mut() {
+            row[k] = T::ZERO;
+        }
+    }
+}
+
+/// Finds the eigenvalues of the leading n by n block of a, destroying it, and
+/// writes them to the first n entries of the output, unsorted. Returns whether
+/// the iteration converged.
+pub(crate) fn eigenvalues<T: Float, const M: usize>(
+    a: &mut [[T; M]],
+    n: usize,
+    output: &mut [Complex<T>],
+) -> bool {
+    balance(a, n);
+    hessenberg(a, n);
+    hqr(a, n, output)
+}
+
+/// Finds the eigenvalues of the upper Hessenberg leading n by n block of a by
+/// Francis' implicit double shift QR algorithm, deflating one real eigenvalue
+/// or a complex conjugate pair whenever a subdiagonal entry becomes negligible.
+/// Follows the EISPACK routine hqr.
+#[allow(clippy::needless_range_loop)]
+fn hqr<T: Float, const M: usize>(a: &mut [[T; M]], n: usize, output: &mut [Complex<T>]) -> bool {
+    let half = T::ONE / (T::ONE + T::ONE);
+    let three_quarters = T::from_usize(3) / T::from_usize(4);
+    let seven_sixteenths = T::from_usize(7) / T::from_usize(16);
+
+    let mut norm = T::ZERO;
+    for (i, row) in a[..n].iter().enumerate() {
+        for e in row[i.saturating_sub(1)..n].iter() {
+            norm += e.abs();
         }
     }
+
+    // The accumulated exceptional shifts
+    let mut t = T::ZERO;
+    let mut end = n;
+    let mut its = 0;
+    while end > 0 {
+        let nn = end - 1;
+
+        // Look for a negligible subdiagonal entry, splitting off the block
+        // from l to nn
+        let mut l = nn;
+        while l > 0 {
+            let mut s = a[l - 1][l - 1].abs() + a[l][l].abs();
+            if s == T::ZERO {
+                s = norm;
+            }
+            if a[l][l - 1].abs() + s == s {
+                a[l][l - 1] = T::ZERO;
+                break;
+            }
+            l -= 1;
+        }
+
+        let mut x = a[nn][nn];
+        if l == nn {
+            output[nn] = Complex::new(x + t, T::ZERO);
+            end -= 1;
+            its = 0;
+            continue;
+        }
+        let mut y = a[nn - 1][nn - 1];
+        let mut w = a[nn][nn - 1] * a[nn - 1][nn];
+        if l == nn - 1 {
+            // A 2 by 2 block, with a real pair or a complex conjugate pair
+            let p = half * (y - x);
+            let q = p * p + w;
+            let z = q.abs().sqrt();
+            x += t;
+            if q >= T::ZERO {
+                let z = p + z.copysign(p);
+                let mut second = x + z;
+                if z != T::ZERO {
+                    second = x - w / z;
+                }
+                output[nn - 1] = Complex::new(x + z, T::ZERO);
+                output[nn] = Complex::new(second, T::ZERO);
+            } else {
+                output[nn - 1] = Complex::new(x + p, -z);
+                output[nn] = Complex::new(x + p, z);
+            }
+            end -= 2;
+            its = 0;
+            continue;
+        }
+
+        if its == QR_MAX_ITERATIONS {
+            return false;
+        }
+        if its == 10 || its == 20 {
+            // An exceptional shift, to break a cycle
+            t += x;
+            for (i, row) in a[..=nn].iter_mut().enumerate() {
+                row[i] -= x;
+            }
+            let s = a[nn][nn - 1].abs() + a[nn - 1][nn - 2].abs();
+            x = three_quarters * s;
+            y = x;
+            w = -seven_sixteenths * s * s;
+        }
+        its += 1;
+
+        // Look for two consecutive small subdiagonal entries, from which to
+        // start the double shift step
+        let mut m = nn - 2;
+        let (mut p, mut q, mut r);
+        loop {
+            let z = a[m][m];
+            r = x - z;
+            let s = y - z;
+            p = (r * s - w) / a[m + 1][m] + a[m][m + 1];
+            q = a[m + 1][m + 1] - z - r - s;
+            r = a[m + 2][m + 1];
+            let s = p.abs() + q.abs() + r.abs();
+            p /= s;
+            q /= s;
+            r /= s;
+            if m == l {
+                break;
+            }
+            let u = a[m][m - 1].abs() * (q.abs() + r.abs());
+            let v = p.abs() * (a[m - 1][m - 1].abs() + z.abs() + a[m + 1][m + 1].abs());
+            if u + v == v {
+                break;
+            }
+            m -= 1;
+        }
+        for i in m + 2..=nn {
+            a[i][i - 2] = T::ZERO;
+            if i != m + 2 {
+                a[i][i - 3] = T::ZERO;
+            }
+        }
+
+        // Chase the bulge down the subdiagonal
+        for k in m..nn {
+            if k != m {
+                p = a[k][k - 1];
+                q = a[k + 1][k - 1];
+                r = if k != nn - 1 {
+                    a[k + 2][k - 1]
+                } else {
+                    T::ZERO
+                };
+                x = p.abs() + q.abs() + r.abs();
+                if x != T::ZERO {
+                    p /= x;
+                    q /= x;
+                    r /= x;
+                }
+            }
+            let s = (p * p + q * q + r * r).sqrt().copysign(p);
+            if s == T::ZERO {
+                continue;
+            }
+            if k == m {
+                if l != m {
+                    a[k][k - 1] = -a[k][k - 1];
+                }
+            } else {
+                a[k][k - 1] = -s * x;
+            }
+            p += s;
+            x = p / s;
+            y = q / s;
+            let z = r / s;
+            q /= p;
+            r /= p;
+            for j in k..=nn {
+                let mut p = a[k][j] + q * a[k + 1][j];
+                if k != nn - 1 {
+                    p += r * a[k + 2][j];
+                    a[k + 2][j] -= p * z;
+                }
+                a[k + 1][j] -= p * y;
+                a[k][j] -= p * x;
+            }
+            let last = if nn < k + 3 { nn } else { k + 3 };
+            for row in a[l..=last].iter_mut() {
+                let mut p = x * row[k] + y * row[k + 1];
+                if k != nn - 1 {
+                    p += z * row[k + 2];
+                    row[k + 2] -= p * r;
+                }
+                row[k + 1] -= p * q;
+                row[k] -= p;
+            }
+        }
+    }
+    true
 }
 
 impl<T: Number<Type = T>, const M: usize, const N: usize, const O: usize> Mul<Matrix<T, O, M>>
@@ -374,10 +737,10 @@ where
         } else {
             // Standard iterative form
             let mut e = [[T::ZERO; O]; N];
-            for i in 0..O {
-                for j in 0..N {
+            for (j, row) in e.iter_mut().enumerate() {
+                for (i, v) in row.iter_mut().enumerate() {
                     for k in 0..M {
-                        e[j][i] += self.e[j][k] * x.e[k][i];
+                        *v += self.e[j][k] * x.e[k][i];
                     }
                 }
             }
@@ -394,9 +757,9 @@ where
 
     fn add(self, x: Matrix<T, M, N>) -> Self::Output {
         let mut e = [[T::ZERO; M]; N];
-        for i in 0..M {
-            for j in 0..N {
-                e[j][i] = self.e[j][i] + x.e[j][i];
+        for (j, row) in e.iter_mut().enumerate() {
+            for (i, v) in row.iter_mut().enumerate() {
+                *v = self.e[j][i] + x.e[j][i];
             }
         }
         Self::Output { e }
@@ -428,6 +791,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::complex::c64;
 
     #[test]
     fn det_2x2() {
@@ -482,6 +846,32 @@ mod tests {
         assert_eq!(a * b, c);
     }
 
+    #[test]
+    fn det_4x4() {
+        // The Vandermonde matrix of 1, 2, 3, 4 has determinant Π (x_j - x_i)
+        let a = Matrix::new([
+            [1., 1., 1., 1.],
+            [1., 2., 4., 8.],
+            [1., 3., 9., 27.],
+            [1., 4., 16., 64.],
+        ]);
+        assert_eq!(a.determinant(), 12.);
+        let b = Matrix::new([
+            [0., 2., 0., 0.],
+            [3., 0., 0., 0.],
+            [0., 0., 0., 5.],
+            [0., 0., 7., 0.],
+        ]);
+        assert_eq!(b.determinant(), 210.);
+        let c = Matrix::new([
+            [1., 2., 3., 4.],
+            [5., 6., 7., 8.],
+            [9., 10., 11., 12.],
+            [13., 14., 15., 16.],
+        ]);
+        assert_eq!(c.determinant(), 0.);
+    }
+
     #[test]
     fn more_matrix_tests_assorted() {
         let x = Matrix::<f32, 2, 3>::new([[1., 2.], [3., 4.], [5., 6.]]);
@@ -510,4 +900,39 @@ mod tests {
         ]);
         assert_eq!(a * b, c);
     }
+
+    fn assert_eigenvalues(e: &[c64], expected: &[c64], tol: f64) {
+        for (a, b) in e.iter().zip(expected) {
+            assert!((*a - *b).norm() < tol, "{:?} != {:?}", e, expected);
+        }
+    }
+
+    #[test]
+    fn eigenvalues() {
+        let m = Matrix::new([[2., 0., 0.], [0., -1., 0.], [0., 0., 5.]]);
+        let e = m.eigenvalues().unwrap();
+        assert_eigenvalues(
+            &e,
+            &[c64::new(-1., 0.), c64::new(2., 0.), c64::new(5., 0.)],
+            1e-14,
+        );
+
+        // A rotation has eigenvalues e^(±iθ)
+        let (s, c) = 0.3_f64.sin_cos();
+        let e = Matrix::new([[c, -s], [s, c]]).eigenvalues().unwrap();
+        assert_eigenvalues(&e, &[c64::new(c, -s), c64::new(c, s)], 1e-14);
+
+        // Reduction keeps the eigenvalues of a full matrix
+        let m = Matrix::new([
+            [4., 1., -2., 2.],
+            [1., 2., 0., 1.],
+            [-2., 0., 3., -2.],
+            [2., 1., -2., -1.],
+        ]);
+        let h = m.hessenberg();
+        assert_eq!(h.e[2][0], 0.);
+        assert_eq!(h.e[3][1], 0.);
+        let trace: f64 = m.eigenvalues().unwrap().iter().map(|z| z.re).sum();
+        assert!((trace - 8.).abs() < 1e-12);
+    }
 }
@@ -0,0 +1,303 @@
+//! Polynomials in the classical orthogonal bases.
+//!
+//! A polynomial of high degree written in the monomial basis has coefficients
+//! that are badly conditioned, as the powers of x grow alike over an interval.
+//! Written instead as a sum of orthogonal polynomials P_k, which oscillate
+//! with bounded amplitude, its coefficients decay with the smoothness of the
+//! function it approximates and it can be evaluated stably by Clenshaw's
+//! recurrence. Chebyshev polynomials suit approximation on [-1, 1], Legendre
+//! polynomials least squares on it, and Hermite and Laguerre polynomials the
+//! weighted real line and half line.
+
+use core::marker::PhantomData;
+
+use crate::{float::Float, number::Number, polynomial::Polynomial};
+
+/// A family of orthogonal polynomials, given by its three-term recurrence
+/// P_(k+1)(x) = (α_k x + β_k) P_k(x) - γ_k P_(k-1)(x), with P_0(x) = 1.
+pub trait Basis {
+    /// The recurrence coefficients (α_k, β_k, γ_k), where γ_0 is unused.
+    fn recurrence<T: Float>(k: usize) -> (T, T, T);
+
+    /// Writes the coefficients of the derivative of the series c into out,
+    /// one shorter.
+    fn derivative<T: Float>(c: &[T], out: &mut [T]);
+}
+
+/// Chebyshev polynomials of the first kind, T_k(cos θ) = cos kθ.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Chebyshev;
+
+/// Legendre polynomials, orthogonal on [-1, 1] with unit weight.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Legendre;
+
+/// Physicists' Hermite polynomials, orthogonal on the real line with weight
+/// exp(-x^2).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Hermite;
+
+/// Laguerre polynomials, orthogonal on [0, ∞) with weight exp(-x).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Laguerre;
+
+impl Basis for Chebyshev {
+    fn recurrence<T: Float>(k: usize) -> (T, T, T) {
+        let two = T::ONE + T::ONE;
+        if k == 0 {
+            (T::ONE, T::ZERO, T::ZERO)
+        } else {
+            (two, T::ZERO, T::ONE)
+        }
+    }
+
+    // T'_(k+1) / (k+1) - T'_(k-1) / (k-1) = 2 T_k
+    fn derivative<T: Float>(c: &[T], out: &mut [T]) {
+        let two = T::ONE + T::ONE;
+        let n = out.len();
+        let (mut d1, mut d2) = (T::ZERO, T::ZERO);
+        for k in (1..=n).rev() {
+            let d = d2 + two * T::from_usize(k) * c[k];
+            out[k - 1] = d;
+            d2 = d1;
+            d1 = d;
+        }
+        if n > 0 {
+            out[0] /= two;
+        }
+    }
+}
+
+impl Basis for Legendre {
+    fn recurrence<T: Float>(k: usize) -> (T, T, T) {
+        let k1 = T::from_usize(k + 1);
+        (
+            T::from_usize(2 * k + 1) / k1,
+            T::ZERO,
+            T::from_usize(k) / k1,
+        )
+    }
+
+    // P'_(k+1) - P'_(k-1) = (2k + 1) P_k, so the kth coefficient of the
+    // derivative is 2k + 1 times the sum of c_(k+1), c_(k+3), ...
+    fn derivative<T: Float>(c: &[T], out: &mut [T]) {
+        let (mut s1, mut s2) = (T::ZERO, T::ZERO);
+        for k in (0..out.len()).rev() {
+            let s = c[k + 1] + s2;
+            out[k] = T::from_usize(2 * k + 1) * s;
+            s2 = s1;
+            s1 = s;
+        }
+    }
+}
+
+impl Basis for Hermite {
+    fn recurrence<T: Float>(k: usize) -> (T, T, T) {
+        let two = T::ONE + T::ONE;
+        (two, T::ZERO, two * T::from_usize(k))
+    }
+
+    // H'_k = 2k H_(k-1)
+    fn derivative<T: Float>(c: &[T], out: &mut [T]) {
+        for (k, e) in out.iter_mut().enumerate() {
+            *e = T::from_usize(2 * (k + 1)) * c[k + 1];
+        }
+    }
+}
+
+impl Basis for Laguerre {
+    fn recurrence<T: Float>(k: usize) -> (T, T, T) {
+        let k1 = T::from_usize(k + 1);
+        (
+            -T::ONE / k1,
+            T::from_usize(2 * k + 1) / k1,
+            T::from_usize(k) / k1,
+        )
+    }
+
+    // L'_k = -(L_0 + L_1 + ... + L_(k-1))
+    fn derivative<T: Float>(c: &[T], out: &mut [T]) {
+        let mut sum = T::ZERO;
+        for k in (1..=out.len()).rev() {
+            sum += c[k];
+            out[k - 1] = -sum;
+        }
+    }
+}
+
+/// A polynomial Σ c_k P_k(x) of degree N - 1 in the orthogonal basis B.
+///
+/// Unlike the monomial Polynomial, coefficients are in ascending order, so
+/// that c[k] multiplies P_k.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct OrthogonalPolynomial<T: Number<Type = T>, B: Basis, const N: usize>
+where
+    T: Float,
+{
+    pub c: [T; N],
+    basis: PhantomData<B>,
+}
+
+impl<T: Number<Type = T>, B: Basis, const N: usize> OrthogonalPolynomial<T, B, N>
+where
+    T: Float,
+{
+    pub const fn new(c: [T; N]) -> Self {
+        Self {
+            c,
+            basis: PhantomData,
+        }
+    }
+
+    /// Evaluates the series at x by Clenshaw's recurrence, which sums the
+    /// P_k backward without forming them and is as stable as evaluating a
+    /// single P_k.
+    pub fn eval(&self, x: T) -> T {
+        let (mut b1, mut b2) = (T::ZERO, T::ZERO);
+        for k in (0..N).rev() {
+            let (a, b, _) = B::recurrence::<T>(k);
+            let (_, _, g) = B::recurrence::<T>(k + 1);
+            let bk = self.c[k] + (a * x + b) * b1 - g * b2;
+            b2 = b1;
+            b1 = bk;
+        }
+        b1
+    }
+
+    /// Returns the derivative, found within the basis.
+    pub fn derivative(&self) -> OrthogonalPolynomial<T, B, { N - 1 }> {
+        let mut c = [T::ZERO; N - 1];
+        B::derivative(&self.c, &mut c);
+        OrthogonalPolynomial::new(c)
+    }
+
+    /// Converts a monomial polynomial into the basis, peeling off the basis
+    /// polynomial matching its leading term from the highest degree down.
+    pub fn from_monomial(p: &Polynomial<T, N>) -> Self {
+        let table = monomials::<T, B, N>();
+        let mut m = [T::ZERO; N];
+        for (e, &k) in m.iter_mut().zip(p.c.iter().rev()) {
+            *e = k;
+        }
+        let mut c = [T::ZERO; N];
+        for k in (0..N).rev() {
+            let a = m[k] / table[k][k];
+            for (e, &t) in m.iter_mut().zip(table[k].iter()).take(k + 1) {
+                *e -= a * t;
+            }
+            c[k] = a;
+        }
+        Self::new(c)
+    }
+
+    /// Converts the series into the monomial basis, which may lose accuracy
+    /// for high degrees.
+    pub fn to_monomial(&self) -> Polynomial<T, N> {
+        let table = monomials::<T, B, N>();
+        let mut c = [T::ZERO; N];
+        for (k, row) in table.iter().enumerate() {
+            for (i, &t) in row.iter().enumerate().take(k + 1) {
+                c[N - 1 - i] += self.c[k] * t;
+            }
+        }
+        Polynomial::new(c)
+    }
+}
+
+/// The ascending monomial coefficients of P_0 to P_(N-1), by the recurrence.
+fn monomials<T: Float, B: Basis, const N: usize>() -> [[T; N]; N] {
+    let mut table = [[T::ZERO; N]; N];
+    if N == 0 {
+        return table;
+    }
+    table[0][0] = T::ONE;
+    for k in 0..N - 1 {
+        let (a, b, g) = B::recurrence::<T>(k);
+        let (prev, next) = table.split_at_mut(k + 1);
+        let p = prev[k];
+        let next = &mut next[0];
+        for i in 0..=k {
+            next[i + 1] += a * p[i];
+            next[i] += b * p[i];
+            if k > 0 {
+                next[i] -= g * prev[k - 1][i];
+            }
+        }
+    }
+    table
+}
+
+impl<T: Number<Type = T>, B: Basis, const N: usize> From<Polynomial<T, N>>
+    for OrthogonalPolynomial<T, B, N>
+where
+    T: Float,
+{
+    fn from(p: Polynomial<T, N>) -> Self {
+        Self::from_monomial(&p)
+    }
+}
+
+impl<T: Number<Type = T>, B: Basis, const N: usize> From<OrthogonalPolynomial<T, B, N>>
+    for Polynomial<T, N>
+where
+    T: Float,
+{
+    fn from(p: OrthogonalPolynomial<T, B, N>) -> Self {
+        p.to_monomial()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: &[f64], b: &[f64], tol: f64) {
+        assert_eq!(a.len(), b.len());
+        for (x, y) in a.iter().zip(b) {
+            assert!((x - y).abs() < tol, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn bases() {
+        // T_3 = 4x^3 - 3x
+        let t = OrthogonalPolynomial::<f64, Chebyshev, 4>::new([0., 0., 0., 1.]);
+        assert_eq!(t.to_monomial(), Polynomial::new([4., 0., -3., 0.]));
+        // P_2 = (3x^2 - 1) / 2
+        let p = OrthogonalPolynomial::<f64, Legendre, 3>::new([0., 0., 1.]);
+        assert_eq!(p.to_monomial(), Polynomial::new([1.5, 0., -0.5]));
+        // H_3 = 8x^3 - 12x
+        let h = OrthogonalPolynomial::<f64, Hermite, 4>::new([0., 0., 0., 1.]);
+        assert_eq!(h.to_monomial(), Polynomial::new([8., 0., -12., 0.]));
+        // L_2 = (x^2 - 4x + 2) / 2
+        let l = OrthogonalPolynomial::<f64, Laguerre, 3>::new([0., 0., 1.]);
+        assert_eq!(l.to_monomial(), Polynomial::new([0.5, -2., 1.]));
+
+        // T_k(cos θ) = cos kθ
+        let t = OrthogonalPolynomial::<f64, Chebyshev, 11>::new([
+            0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1.,
+        ]);
+        for theta in [0.1, 0.7, 2.3] {
+            assert!((t.eval(f64::cos(theta)) - f64::cos(10. * theta)).abs() < 1e-13);
+        }
+    }
+
+    fn check_basis<B: Basis>() {
+        let p = Polynomial::new([0.5, -1., 2., 3., -4., 1.]);
+        let q = OrthogonalPolynomial::<f64, B, 6>::from_monomial(&p);
+        assert_close(&q.to_monomial().c, &p.c, 1e-12);
+        for x in [-1.5, -0.3, 0., 0.8, 2.] {
+            assert!((q.eval(x) - p.eval(x)).abs() < 1e-12);
+        }
+        let d: Polynomial<f64, 5> = q.derivative().into();
+        assert_close(&d.c, &p.derivative().c, 1e-12);
+    }
+
+    #[test]
+    fn conversion_and_derivative() {
+        check_basis::<Chebyshev>();
+        check_basis::<Legendre>();
+        check_basis::<Hermite>();
+        check_basis::<Laguerre>();
+    }
+}
@@ -1,8 +1,54 @@
-use crate::solvers;
+use std::ops::{Add, Div, Mul, Neg, Sub};
 
-use crate::{float::Float, number::Number};
+use crate::solvers::{self, RootFinder, RootSet};
 
-#[derive(Copy, Clone, Debug)]
+use crate::{
+    complex::{c32, c64},
+    fit,
+    float::Float,
+    interpolation,
+    matrix::{self, Matrix},
+    number::Number,
+};
+
+mod format;
+
+pub use format::{Latex, ParsePolynomialError, Unicode};
+
+/// Length of the coefficient array holding the sum of polynomials with `a` and
+/// `b` coefficients.
+pub const fn max_len(a: usize, b: usize) -> usize {
+    if a > b {
+        a
+    } else {
+        b
+    }
+}
+
+/// The resultant of p and q, the determinant of their Sylvester matrix, which
+/// vanishes exactly when they share a root or both leading coefficients
+/// vanish. For p of degree m with leading coefficient a and roots α_i, and q
+/// of degree n with leading coefficient b and roots β_j, it is
+/// a^n b^m Π (α_i - β_j). Degrees are those of the polynomials, not of their
+/// coefficient arrays, and the resultant with the zero polynomial is zero.
+///
+/// Found by Bareiss' fraction-free elimination, so polynomials with integer
+/// coefficients have their resultant found exactly as long as each product of
+/// two intermediate minors fits in the mantissa, below 2^53 for f64. Larger or
+/// non-integer coefficients give a rounded resultant with no error bound, so
+/// a small nonzero value doesn't rule out a common root.
+pub fn resultant<T: Float, const N: usize, const M: usize>(
+    p: &Polynomial<T, N>,
+    q: &Polynomial<T, M>,
+) -> T
+where
+    [(); N + M]:,
+{
+    let mut a = [[T::ZERO; N + M]; N + M];
+    resultant_in_place(&p.c, &q.c, &mut a)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Polynomial<T: Number<Type = T>, const N: usize>
 where
     T: Float,
@@ -24,89 +70,657 @@ where
         Self { c }
     }
 
+    /// Returns the composition p(q(x)).
+    pub fn compose<const M: usize>(
+        &self,
+        q: &Polynomial<T, M>,
+    ) -> Polynomial<T, { (N - 1) * (M - 1) + 1 }>
+    where
+        [(); M]:,
+        [(); (N - 1) * (M - 1) + 1]:,
+    {
+        // Horner's scheme over polynomials, accumulated in ascending order
+        let mut acc = [T::ZERO; (N - 1) * (M - 1) + 1];
+        for &k in self.c.iter() {
+            let mut next = [T::ZERO; (N - 1) * (M - 1) + 1];
+            for (i, &a) in acc.iter().enumerate() {
+                for (j, &b) in q.c.iter().rev().enumerate() {
+                    if i + j < next.len() {
+                        next[i + j] += a * b;
+                    }
+                }
+            }
+            next[0] += k;
+            acc = next;
+        }
+        acc.reverse();
+        Polynomial { c: acc }
+    }
+
+    /// Returns the derivative of the polynomial.
+    pub fn derivative(&self) -> Polynomial<T, { N - 1 }>
+    where
+        [(); N - 1]:,
+    {
+        let mut c = [T::ZERO; N - 1];
+        for (i, e) in c.iter_mut().enumerate() {
+            *e = self.c[i] * T::from_usize(N - 1 - i);
+        }
+        Polynomial { c }
+    }
+
+    /// Returns the antiderivative of the polynomial with constant of
+    /// integration k.
+    pub fn antiderivative(&self, k: T) -> Polynomial<T, { N + 1 }>
+    where
+        [(); N + 1]:,
+    {
+        let mut c = [k; N + 1];
+        for (i, e) in c.iter_mut().take(N).enumerate() {
+            *e = self.c[i] / T::from_usize(N - i);
+        }
+        Polynomial { c }
+    }
+
+    /// Returns the definite integral of the polynomial over [a, b].
+    pub fn integrate(&self, a: T, b: T) -> T
+    where
+        [(); N + 1]:,
+    {
+        let p = self.antiderivative(T::ZERO);
+        p.eval(b) - p.eval(a)
+    }
+
+    /// Evaluates the polynomial and its first K derivatives at x in a single
+    /// Horner pass, returning [p(x), p'(x), ..., p^(K)(x)].
+    pub fn eval_with_derivatives<const K: usize>(&self, x: T) -> [T; K + 1] {
+        let mut output = [T::ZERO; K + 1];
+        for (i, &k) in self.c.iter().enumerate() {
+            for j in (1..=K.min(i)).rev() {
+                output[j] = output[j] * x + output[j - 1];
+            }
+            output[0] = output[0] * x + k;
+        }
+
+        // The recurrence yields p^(j)(x) / j!
+        let mut factorial = T::ONE;
+        for (j, e) in output.iter_mut().enumerate().skip(2) {
+            factorial *= T::from_usize(j);
+            *e *= factorial;
+        }
+        output
+    }
+
+    /// Evaluates the polynomial at x using Horner's scheme.
+    pub fn eval(&self, x: T) -> T {
+        self.c.iter().fold(T::ZERO, |acc, &k| acc * x + k)
+    }
+
+    #[deprecated(note = "use eval, which takes any degree")]
     pub fn eval_quadratic(&self, x: T) -> T {
-        self.c[0] * x.powi(2) + self.c[1] * x + self.c[2]
+        self.eval(x)
     }
 
+    #[deprecated(note = "use eval, which takes any degree")]
     pub fn eval_cubic(&self, x: T) -> T {
-        self.c[0] * x.powi(3) + self.c[1] * x.powi(2) + self.c[2] * x + self.c[3]
+        self.eval(x)
     }
 
+    #[deprecated(note = "use eval, which takes any degree")]
     pub fn eval_quartic(&self, x: T) -> T {
-        self.c[0] * x.powi(4)
-            + self.c[1] * x.powi(3)
-            + self.c[2] * x.powi(2)
-            + self.c[3] * x
-            + self.c[4]
+        self.eval(x)
     }
 
-    pub fn eval(&self, x: T) -> T {
+    /// Evaluates the polynomial at x using the compensated Horner scheme of
+    /// Graillat, Langlois and Louvet, returning the value together with a
+    /// bound on its absolute error.
+    ///
+    /// The rounding errors of each Horner step are captured exactly with
+    /// error-free transformations (TwoSum/TwoProd) and accumulated in a second
+    /// Horner pass, so the result is as accurate as if it had been computed in
+    /// twice the working precision. The bound is the a posteriori bound of
+    /// Langlois and Louvet, computed alongside the evaluation. Requires a real
+    /// floating point type.
+    ///
+    /// # Panics
+    ///
+    /// For c32 and c64, whose abs and mul_add are unimplemented.
+    pub fn eval_compensated(&self, x: T) -> (T, T) {
+        if N == 0 {
+            return (T::ZERO, T::ZERO);
+        }
+        let two = T::ONE + T::ONE;
+        let u = T::EPSILON / two;
+        let x_abs = x.abs();
+
+        let mut h = self.c[0];
+        let mut c = T::ZERO;
+        let mut b = T::ZERO;
+        for &k in self.c.iter().skip(1) {
+            let (p, pi) = two_prod(h, x);
+            let (s, sigma) = two_sum(p, k);
+            h = s;
+            c = c * x + (pi + sigma);
+            b = b * x_abs + (pi.abs() + sigma.abs());
+        }
+        let value = h + c;
+
+        let n = T::from_usize(N - 1);
+        let gamma = |k: T| (k * u) / (T::ONE - k * u);
+        let four = two + two;
+        let bound = (u * value.abs() + (gamma(four * n + two) * b + two * u * u * value.abs()))
+            / (T::ONE - two * (n + T::ONE) * u);
+        (value, bound)
+    }
+
+    /// Divides by d using long division, returning the quotient and remainder.
+    /// The leading coefficient of d must be nonzero.
+    pub fn div_rem<const M: usize>(
+        &self,
+        d: &Polynomial<T, M>,
+    ) -> (Polynomial<T, { N - M + 1 }>, Polynomial<T, { M - 1 }>)
+    where
+        [(); M]:,
+        [(); N - M + 1]:,
+        [(); M - 1]:,
+    {
+        let mut r = self.c;
+        div_rem_in_place(&mut r, &d.c);
+        let mut q = [T::ZERO; N - M + 1];
+        q.copy_from_slice(&r[..N - M + 1]);
+        let mut s = [T::ZERO; M - 1];
+        s.copy_from_slice(&r[N - M + 1..]);
+        (Polynomial { c: q }, Polynomial { c: s })
+    }
+
+    /// Deflates the polynomial by the linear factor (x - r) using synthetic
+    /// division, returning the quotient and the remainder p(r).
+    pub fn deflate(&self, r: T) -> (Polynomial<T, { N - 1 }>, T)
+    where
+        [(); N - 1]:,
+    {
+        let mut q = [T::ZERO; N - 1];
+        let mut acc = T::ZERO;
+        for (i, &k) in self.c.iter().enumerate() {
+            acc = acc * r + k;
+            if i < N - 1 {
+                q[i] = acc;
+            }
+        }
+        (Polynomial { c: q }, acc)
+    }
+
+    /// Deflates a real polynomial by the quadratic factor
+    /// x^2 - 2re·x + (re^2 + im^2) of the complex-conjugate roots re ± im·i,
+    /// returning the quotient and the linear remainder.
+    pub fn deflate_conjugate(&self, re: T, im: T) -> (Polynomial<T, { N - 2 }>, Polynomial<T, 2>)
+    where
+        [(); N - 2]:,
+    {
+        let factor = Polynomial::new([T::ONE, -(re + re), re * re + im * im]);
+        let mut r = self.c;
+        div_rem_in_place(&mut r, &factor.c);
+        let mut q = [T::ZERO; N - 2];
+        q.copy_from_slice(&r[..N - 2]);
+        (Polynomial { c: q }, Polynomial::new([r[N - 2], r[N - 1]]))
+    }
+
+    /// Returns the monic greatest common divisor of the two polynomials by the
+    /// Euclidean algorithm, padded with leading zeros.
+    ///
+    /// Remainders are normalized at each step, and a remainder whose
+    /// coefficients are all within tol of zero is taken to be zero, so
+    /// polynomials that share roots only approximately still have a nontrivial
+    /// divisor.
+    pub fn gcd<const M: usize>(
+        &self,
+        other: &Polynomial<T, M>,
+        tol: T,
+    ) -> Polynomial<T, { max_len(N, M) }>
+    where
+        [(); M]:,
+        [(); max_len(N, M)]:,
+    {
+        let mut a = [T::ZERO; max_len(N, M)];
+        let mut b = [T::ZERO; max_len(N, M)];
+        a[max_len(N, M) - N..].copy_from_slice(&self.c);
+        b[max_len(N, M) - M..].copy_from_slice(&other.c);
+        gcd_in_place(&mut a, &mut b, tol);
+        Polynomial { c: a }
+    }
+
+    /// The resultant with q, as described by the free function.
+    #[inline]
+    pub fn resultant<const M: usize>(&self, q: &Polynomial<T, M>) -> T
+    where
+        [(); N + M]:,
+    {
+        resultant(self, q)
+    }
+
+    /// The discriminant, (-1)^(n(n-1)/2) res(p, p') / a for a polynomial of
+    /// degree n with leading coefficient a, which is a^(2n-2) times the
+    /// product of the squared differences of the roots. It vanishes exactly
+    /// when the polynomial has a multiple root, and otherwise its sign gives
+    /// the parity of the number of pairs of complex roots, so that
+    /// b^2 - 4ac > 0 for two real roots of a quadratic, and a positive
+    /// discriminant of a cubic means three real roots. That of a linear
+    /// polynomial is 1, and of a constant 0. Exact for integer coefficients
+    /// only as far as the resultant is, so a rounded discriminant near zero
+    /// doesn't settle whether a root is multiple.
+    pub fn discriminant(&self) -> T
+    where
+        [(); 2 * N]:,
+    {
+        let s = self.c.iter().position(|&k| k != T::ZERO).unwrap_or(N);
+        if s == N {
+            return T::ZERO;
+        }
+        let c = &self.c[s..];
+        let n = c.len() - 1;
+        let mut dp = [T::ZERO; N];
+        derivative_in_place(c, &mut dp[..=n]);
+        let mut a = [[T::ZERO; 2 * N]; 2 * N];
+        let r = resultant_in_place(c, &dp[..=n], &mut a);
+        let sign = if n * n.saturating_sub(1) / 2 % 2 == 1 {
+            -T::ONE
+        } else {
+            T::ONE
+        };
+        sign * r / c[0]
+    }
+
+    /// Returns the square-free factorization of the polynomial by Yun's
+    /// algorithm, such that p = k × f_1 × f_2^2 × ... × f_(N-1)^(N-1) for some
+    /// constant k.
+    ///
+    /// Each factor f_i is monic, padded with leading zeros, and is the product
+    /// of the (x - r) over all roots r of multiplicity exactly i. Multiplicities
+    /// with no roots have the factor 1.
+    pub fn square_free(&self, tol: T) -> [Polynomial<T, N>; N - 1]
+    where
+        [(); N - 1]:,
+    {
+        let mut output = [Polynomial { c: [T::ZERO; N] }; N - 1];
+        for f in output.iter_mut() {
+            f.c[N - 1] = T::ONE;
+        }
+
+        // a = gcd(p, p'), b = p / a, d = p' / a - b'
+        let mut dp = [T::ZERO; N];
+        derivative_in_place(&self.c, &mut dp);
+        let mut a = self.c;
+        let mut scratch = dp;
+        gcd_in_place(&mut a, &mut scratch, tol);
+        let mut b = [T::ZERO; N];
+        div_exact(&self.c, &a, &mut b, tol);
+        let mut c = [T::ZERO; N];
+        div_exact(&dp, &a, &mut c, tol);
+        let mut d = [T::ZERO; N];
+        derivative_in_place(&b, &mut d);
+        for (e, k) in d.iter_mut().zip(c) {
+            *e = k - *e;
+        }
+
+        for f in output.iter_mut() {
+            if degree(&b, tol) == 0 {
+                break;
+            }
+            // f = gcd(b, d), b = b / f, d = d / f - b'
+            let mut g = b;
+            let mut h = d;
+            gcd_in_place(&mut g, &mut h, tol);
+            let mut next = [T::ZERO; N];
+            div_exact(&b, &g, &mut next, tol);
+            b = next;
+            div_exact(&d, &g, &mut c, tol);
+            derivative_in_place(&b, &mut d);
+            for (e, k) in d.iter_mut().zip(c) {
+                *e = k - *e;
+            }
+            f.c = g;
+        }
+        output
+    }
+
+    /// Fits the polynomial to the samples (xs, ys) by least squares, as
+    /// described by the fit module. Returns None unless there are at least N
+    /// distinct abscissae.
+    #[inline]
+    pub fn fit(xs: &[T], ys: &[T]) -> Option<fit::Fit<T, N>> {
+        fit::fit(xs, ys)
+    }
+
+    /// Fits the polynomial to the samples (xs, ys) by least squares, with
+    /// each squared residual scaled by its weight in ws.
+    #[inline]
+    pub fn fit_weighted(xs: &[T], ys: &[T], ws: &[T]) -> Option<fit::Fit<T, N>> {
+        fit::fit_weighted(xs, ys, ws)
+    }
+
+    /// The unique polynomial through the points (xs, ys), by Newton's divided
+    /// differences. Returns None if any abscissae repeat.
+    #[inline]
+    pub fn interpolate(xs: &[T; N], ys: &[T; N]) -> Option<Self> {
+        interpolation::newton(xs, ys)
+    }
+
+    /// The companion matrix, whose eigenvalues are the roots. Requires a
+    /// nonzero leading coefficient.
+    #[inline]
+    pub fn companion(&self) -> Matrix<T, { N - 1 }, { N - 1 }> {
+        solvers::companion::companion(self)
+    }
+
+    /// Finds the real roots, in closed form up to quadratics and by Yuksel's
+    /// method otherwise.
+    pub fn roots(&self, tol: T) -> RootSet<T, { N - 1 }> {
         match N {
-            1 => self.c[0],
-            2 => self.c[0] * x + self.c[1],
-            3 => self.eval_quadratic(x),
-            4 => self.eval_cubic(x),
-            5 => self.eval_quartic(x),
-            // _ => self
-            //     .c
-            //     .iter()
-            //     .enumerate()
-            //     .map(|(n, k)| k * x.powi((N - n) as i32))
-            //     .sum::<f64>(),
-            _ => todo!(),
-        }
-    }
-
-    pub fn roots(&self, tol: T) -> [T; N + 0_usize.pow(N as u32 - 1) - 1] {
-        let mut output = [T::NAN; N + 0_usize.pow(N as u32 - 1) - 1];
-        let roots = match N {
             1 => self.root_constant(tol),
-            2 => self.root_linear(tol),
+            2 => self.root_linear(),
             3 => solvers::blinn::Blinn::roots_quadratic(self),
-            // 4 => solvers::yuksel::roots_cubic(self),
-            _ => [T::NAN; N + 0_usize.pow(N as u32 - 1) - 1],
-        };
-        for (i, r) in roots.iter().enumerate().take(N) {
-            output[i] = *r;
+            _ => solvers::yuksel::roots(self, tol),
         }
-        output
+    }
+
+    /// Finds the real roots with the given solver, so that solvers can be
+    /// swapped per call site, as in `p.roots_with::<Yuksel>(tol)`.
+    #[inline]
+    pub fn roots_with<S: RootFinder>(&self, tol: T) -> RootSet<T, { N - 1 }> {
+        S::roots(self, tol)
     }
 
     #[inline]
-    fn root_constant(&self, tol: T) -> [T; N + 0_usize.pow(N as u32 - 1) - 1] {
-        // Constant polynomial, has a root at x=0 IFF p(x) = 0
-        let mut output = [T::NAN; N + 0_usize.pow(N as u32 - 1) - 1];
+    fn root_constant(&self, tol: T) -> RootSet<T, { N - 1 }> {
+        // Constant polynomial, has no isolated roots, and every x is a root
+        // IFF p(x) = 0, which the set can't hold
+        let mut output = RootSet::new();
         if self.c[0].abs() <= tol {
-            output[0] = T::ZERO;
+            output.fail();
         }
         output
     }
 
     #[inline]
-    fn root_linear(&self, _tol: T) -> [T; N + 0_usize.pow(N as u32 - 1) - 1] {
+    fn root_linear(&self) -> RootSet<T, { N - 1 }> {
         // Linear polynomial, has exactly one root at x = -b/a
-        [-self.c[1] / self.c[0]; N + 0_usize.pow(N as u32 - 1) - 1]
+        let mut output = RootSet::new();
+        output.push(-self.c[1] / self.c[0]);
+        output
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize, const M: usize> Add<Polynomial<T, M>> for Polynomial<T, N>
+where
+    T: Float,
+    [(); N]:,
+    [(); M]:,
+    [(); max_len(N, M)]:,
+{
+    type Output = Polynomial<T, { max_len(N, M) }>;
+
+    fn add(self, rhs: Polynomial<T, M>) -> Self::Output {
+        // Coefficients are aligned on the constant term
+        let mut c = [T::ZERO; max_len(N, M)];
+        for (i, &k) in self.c.iter().enumerate() {
+            c[max_len(N, M) - N + i] += k;
+        }
+        for (i, &k) in rhs.c.iter().enumerate() {
+            c[max_len(N, M) - M + i] += k;
+        }
+        Self::Output { c }
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize, const M: usize> Sub<Polynomial<T, M>> for Polynomial<T, N>
+where
+    T: Float,
+    [(); N]:,
+    [(); M]:,
+    [(); max_len(N, M)]:,
+{
+    type Output = Polynomial<T, { max_len(N, M) }>;
+
+    fn sub(self, rhs: Polynomial<T, M>) -> Self::Output {
+        let mut c = [T::ZERO; max_len(N, M)];
+        for (i, &k) in self.c.iter().enumerate() {
+            c[max_len(N, M) - N + i] += k;
+        }
+        for (i, &k) in rhs.c.iter().enumerate() {
+            c[max_len(N, M) - M + i] -= k;
+        }
+        Self::Output { c }
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize, const M: usize> Mul<Polynomial<T, M>> for Polynomial<T, N>
+where
+    T: Float,
+    [(); N]:,
+    [(); M]:,
+    [(); N + M - 1]:,
+{
+    type Output = Polynomial<T, { N + M - 1 }>;
+
+    fn mul(self, rhs: Polynomial<T, M>) -> Self::Output {
+        let mut c = [T::ZERO; N + M - 1];
+        for (i, &a) in self.c.iter().enumerate() {
+            for (j, &b) in rhs.c.iter().enumerate() {
+                c[i + j] += a * b;
+            }
+        }
+        Self::Output { c }
     }
 }
 
-impl<T: Number<Type = T>, const N: usize> core::fmt::Display for Polynomial<T, N>
+impl<T: Number<Type = T>, const N: usize> Mul<T> for Polynomial<T, N>
 where
     T: Float,
     [(); N]:,
 {
-    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let mut output = String::new();
-        for i in 0..N {
-            if i != (N - 1) {
-                output.push_str(&format!("{}×x^{} + ", self.c[i], (N - 1) - i));
-            } else {
-                output.push_str(&format!("{}", self.c[i]));
+    type Output = Polynomial<T, N>;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        let mut c = self.c;
+        for e in c.iter_mut() {
+            *e *= rhs;
+        }
+        Self::Output { c }
+    }
+}
+
+macro_rules! impl_scalar_mul_for_polynomial {
+    ($($type: ty),+) => {
+        $(
+            impl<const N: usize> Mul<Polynomial<$type, N>> for $type {
+                type Output = Polynomial<$type, N>;
+
+                fn mul(self, rhs: Polynomial<$type, N>) -> Self::Output {
+                    rhs * self
+                }
             }
+        )+
+    };
+}
+
+impl_scalar_mul_for_polynomial!(f32, f64, c32, c64);
+
+impl<T: Number<Type = T>, const N: usize> Div<T> for Polynomial<T, N>
+where
+    T: Float,
+    [(); N]:,
+{
+    type Output = Polynomial<T, N>;
+
+    fn div(self, rhs: T) -> Self::Output {
+        let mut c = self.c;
+        for e in c.iter_mut() {
+            *e /= rhs;
+        }
+        Self::Output { c }
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize> Neg for Polynomial<T, N>
+where
+    T: Float,
+    [(); N]:,
+{
+    type Output = Polynomial<T, N>;
+
+    fn neg(self) -> Self::Output {
+        let mut c = self.c;
+        for e in c.iter_mut() {
+            *e = -*e;
         }
-        f.write_str(&output)
+        Self::Output { c }
+    }
+}
+
+/// Error-free transformation of a sum, such that a + b = s + e exactly.
+#[inline]
+fn two_sum<T: Float>(a: T, b: T) -> (T, T) {
+    let s = a + b;
+    let z = s - a;
+    let e = (a - (s - z)) + (b - z);
+    (s, e)
+}
+
+/// Error-free transformation of a product, such that a × b = p + e exactly.
+#[inline]
+fn two_prod<T: Float>(a: T, b: T) -> (T, T) {
+    let p = a * b;
+    let e = a.mul_add(b, -p);
+    (p, e)
+}
+
+/// Index of the leading coefficient, the first whose magnitude exceeds tol.
+/// Returns the slice length for the zero polynomial.
+#[inline]
+pub(crate) fn leading<T: Float>(c: &[T], tol: T) -> usize {
+    c.iter().position(|k| k.abs() > tol).unwrap_or(c.len())
+}
+
+/// Degree of the polynomial with the given coefficients, where the zero
+/// polynomial is taken to have degree 0.
+#[inline]
+fn degree<T: Float>(c: &[T], tol: T) -> usize {
+    (c.len() - leading(c, tol)).saturating_sub(1)
+}
+
+/// Scales the coefficients to a maximum magnitude of one, returning the
+/// original maximum magnitude.
+pub(crate) fn normalize<T: Float>(c: &mut [T]) -> T {
+    let scale = c
+        .iter()
+        .fold(T::ZERO, |m, k| if k.abs() > m { k.abs() } else { m });
+    if scale > T::ZERO {
+        for e in c.iter_mut() {
+            *e /= scale;
+        }
+    }
+    scale
+}
+
+/// Writes the derivative of c into out, both right-aligned and of equal length.
+pub(crate) fn derivative_in_place<T: Float>(c: &[T], out: &mut [T]) {
+    let n = c.len();
+    out.fill(T::ZERO);
+    for (i, &k) in c.iter().enumerate().take(n.saturating_sub(1)) {
+        out[i + 1] = k * T::from_usize(n - 1 - i);
     }
 }
 
+/// Long division of num by den, leaving the quotient in the leading
+/// num.len() - den.len() + 1 entries of num and the remainder in the rest.
+pub(crate) fn div_rem_in_place<T: Float>(num: &mut [T], den: &[T]) {
+    if num.len() < den.len() {
+        return;
+    }
+    let lead = den[0].recip();
+    for i in 0..=(num.len() - den.len()) {
+        let q = num[i] * lead;
+        num[i] = q;
+        for (j, &k) in den.iter().enumerate().skip(1) {
+            num[i + j] -= q * k;
+        }
+    }
+}
+
+/// Divides num by den, ignoring leading coefficients within tol of zero and
+/// discarding the remainder, and writes the quotient into out right-aligned.
+/// The output must be the same length as num.
+pub(crate) fn div_exact<T: Float>(num: &[T], den: &[T], out: &mut [T], tol: T) {
+    let n = out.len();
+    let ln = leading(num, tol);
+    let ld = leading(den, tol);
+    out.copy_from_slice(num);
+    if ln == n || ld == den.len() || n - ln < den.len() - ld {
+        out.fill(T::ZERO);
+        return;
+    }
+    div_rem_in_place(&mut out[ln..], &den[ld..]);
+    let q = (n - ln) - (den.len() - ld) + 1;
+    out.copy_within(ln..ln + q, n - q);
+    out[..n - q].fill(T::ZERO);
+}
+
+/// The resultant of two coefficient slices, from the determinant of their
+/// Sylvester matrix built in the zeroed leading block of a.
+pub(crate) fn resultant_in_place<T: Float, const K: usize>(
+    p: &[T],
+    q: &[T],
+    a: &mut [[T; K]],
+) -> T {
+    let p = &p[p.iter().position(|&k| k != T::ZERO).unwrap_or(p.len())..];
+    let q = &q[q.iter().position(|&k| k != T::ZERO).unwrap_or(q.len())..];
+    if p.is_empty() || q.is_empty() {
+        return T::ZERO;
+    }
+    let (m, n) = (p.len() - 1, q.len() - 1);
+
+    // n shifted rows of p over m shifted rows of q
+    for (i, row) in a[..n].iter_mut().enumerate() {
+        row[i..=i + m].copy_from_slice(p);
+    }
+    for (i, row) in a[n..n + m].iter_mut().enumerate() {
+        row[i..=i + n].copy_from_slice(q);
+    }
+    matrix::bareiss(a, m + n)
+}
+
+/// Euclidean algorithm over two right-aligned coefficient slices of equal
+/// length, leaving the monic greatest common divisor in a.
+pub(crate) fn gcd_in_place<T: Float>(a: &mut [T], b: &mut [T], tol: T) {
+    let n = a.len();
+    normalize(a);
+    normalize(b);
+    if leading(a, tol) > leading(b, tol) {
+        a.swap_with_slice(b);
+    }
+    loop {
+        let lb = leading(b, tol);
+        if lb == n {
+            break;
+        }
+        let la = leading(a, tol);
+        div_rem_in_place(&mut a[la..], &b[lb..]);
+        a[..lb + 1].fill(T::ZERO);
+        if normalize(a) <= tol {
+            a.fill(T::ZERO);
+        }
+        a.swap_with_slice(b);
+    }
+    let la = leading(a, tol);
+    if la < n {
+        let lead = a[la].recip();
+        for e in a[la..].iter_mut() {
+            *e *= lead;
+        }
+    }
+    a[..la].fill(T::ZERO);
+}
+
 #[cfg(test)]
 mod tests {
     use crate::complex::c32;
@@ -138,9 +752,9 @@ mod tests {
         assert_eq!(p_3.c, [1.0, 2.0, 3.0, 4.0]);
 
         assert_eq!(p_0.to_string(), "1");
-        assert_eq!(p_1.to_string(), "1×x^1 + 2");
-        assert_eq!(p_2.to_string(), "1×x^2 + 2×x^1 + 3");
-        assert_eq!(p_3.to_string(), "1×x^3 + 2×x^2 + 3×x^1 + 4");
+        assert_eq!(p_1.to_string(), "x + 2");
+        assert_eq!(p_2.to_string(), "x^2 + 2x + 3");
+        assert_eq!(p_3.to_string(), "x^3 + 2x^2 + 3x + 4");
 
         assert_eq!(p_0.eval(-3.0), 1.0);
         assert_eq!(p_0.eval(0.0), 1.0);
@@ -163,21 +777,317 @@ mod tests {
         assert_eq!(p_3.eval(2.0), 26.0);
     }
 
+    #[test]
+    fn eval_horner() {
+        // p(x) = (x - 1)(x - 2)(x - 3)(x - 4)(x - 5)
+        let p = Polynomial::new([1., -15., 85., -225., 274., -120.]);
+        assert_eq!(p.eval(0.), -120.);
+        assert_eq!(p.eval(3.), 0.);
+        assert_eq!(p.eval(6.), 120.);
+
+        // p(x) = x^7 - 1
+        let q = Polynomial::new([1., 0., 0., 0., 0., 0., 0., -1.]);
+        assert_eq!(q.eval(1.), 0.);
+        assert_eq!(q.eval(2.), 127.);
+        assert_eq!(q.eval(-1.), -2.);
+
+        let r = Polynomial::<f64, 0>::new([]);
+        assert_eq!(r.eval(5.), 0.);
+
+        let s = Polynomial::new([c32::new(1.0, 0.0), c32::new(0.0, 1.0)]);
+        assert_eq!(s.eval(c32::new(0.0, 1.0)), c32::new(0.0, 2.0));
+
+        // The fixed-degree evaluations defer to Horner
+        #[allow(deprecated)]
+        {
+            assert_eq!(Polynomial::new([2., -3., 1.]).eval_quadratic(3.), 10.);
+            assert_eq!(Polynomial::new([1., 5., -14., 0.]).eval_cubic(4.), 88.);
+            assert_eq!(Polynomial::new([1., 0., 0., 0., -1.]).eval_quartic(2.), 15.);
+        }
+    }
+
+    #[test]
+    fn eval_compensated() {
+        // p(x) = (x - 1)^7 expanded, evaluated next to its root where plain
+        // Horner loses nearly every significant digit to cancellation
+        let p = Polynomial::new([1., -7., 21., -35., 35., -21., 7., -1.]);
+        let x = 1.0 + 1.0 / 64.0;
+        let exact = (x - 1.0_f64).powi(7);
+
+        let (y, bound) = p.eval_compensated(x);
+        assert!((y - exact).abs() <= bound);
+        assert!((y - exact).abs() <= 4. * f64::EPSILON * exact.abs());
+
+        // Exact arithmetic has a zero error bound beyond the final rounding
+        let q = Polynomial::new([1., 5., -14., 0.]);
+        let (y, bound) = q.eval_compensated(4.0);
+        assert_eq!(y, 88.);
+        assert!(bound < 1e-12);
+
+        let r = Polynomial::new([2.0_f32, -3.0, 1.0]);
+        let (y, _) = r.eval_compensated(0.5);
+        assert_eq!(y, 0.);
+    }
+
+    #[test]
+    fn arithmetic() {
+        let p = Polynomial::new([1., 5., -14., 0.]);
+        let q = Polynomial::new([2., -1.]);
+
+        assert_eq!((p + q).c, [1., 5., -12., -1.]);
+        assert_eq!((q + p).c, [1., 5., -12., -1.]);
+        assert_eq!((p - q).c, [1., 5., -16., 1.]);
+        assert_eq!((q - p).c, [-1., -5., 16., -1.]);
+        assert_eq!((p * q).c, [2., 9., -33., 14., 0.]);
+        assert_eq!((q * p).c, [2., 9., -33., 14., 0.]);
+        assert_eq!((p * 2.).c, [2., 10., -28., 0.]);
+        assert_eq!(2. * p, Polynomial::new([2., 10., -28., 0.]));
+        assert_eq!((p / 2.).c, [0.5, 2.5, -7., 0.]);
+        assert_eq!((-q).c, [-2., 1.]);
+
+        // (x + 7)(x - 2)x, built from its characteristic factors
+        let r = Polynomial::new([1., 7.]) * Polynomial::new([1., -2.]) * Polynomial::new([1., 0.]);
+        assert_eq!(r, p);
+        for x in [-3., 0.5, 4.] {
+            assert_eq!((p * q).eval(x), p.eval(x) * q.eval(x));
+        }
+
+        let a = Polynomial::new([c32::new(1.0, 0.0), c32::new(0.0, 1.0)]);
+        let b = Polynomial::new([c32::new(1.0, 0.0), c32::new(0.0, -1.0)]);
+        assert_eq!(
+            (a * b).c,
+            [c32::new(1.0, 0.0), c32::new(0.0, 0.0), c32::new(1.0, 0.0)]
+        );
+        assert_eq!(
+            (c32::new(0.0, 2.0) * a).c,
+            [c32::new(0.0, 2.0), c32::new(-2.0, 0.0)]
+        );
+        assert_eq!((2_f32 * Polynomial::new([1_f32, -3.])).c, [2., -6.]);
+    }
+
+    #[test]
+    fn composition() {
+        // p(q(x)) with p(x) = x^2 + 1 and q(x) = 2x - 3
+        let p = Polynomial::new([1., 0., 1.]);
+        let q = Polynomial::new([2., -3.]);
+        assert_eq!(p.compose(&q).c, [4., -12., 10.]);
+        assert_eq!(q.compose(&p).c, [2., 0., -1.]);
+
+        let r = Polynomial::new([1., 5., -14., 0.]);
+        let s = Polynomial::new([1., 0., -1.]);
+        let t = r.compose(&s);
+        assert_eq!(t.c.len(), 7);
+        for x in [-2., -0.5, 0., 1.5, 3.] {
+            assert_eq!(t.eval(x), r.eval(s.eval(x)));
+        }
+
+        // Composition with a constant is a constant
+        let k = Polynomial::new([4.]);
+        assert_eq!(r.compose(&k).c, [88.]);
+    }
+
+    #[test]
+    fn division() {
+        // (x^3 + 5x^2 - 14x) / (x - 2) = x^2 + 7x
+        let p = Polynomial::new([1., 5., -14., 0.]);
+        let (q, r) = p.div_rem(&Polynomial::new([1., -2.]));
+        assert_eq!(q.c, [1., 7., 0.]);
+        assert_eq!(r.c, [0.]);
+
+        // (x^3 + 5x^2 - 14x) / (2x^2 + 1) = 0.5x + 2.5 rem -14.5x - 2.5
+        let (q, r) = p.div_rem(&Polynomial::new([2., 0., 1.]));
+        assert_eq!(q.c, [0.5, 2.5]);
+        assert_eq!(r.c, [-14.5, -2.5]);
+        assert_eq!((q * Polynomial::new([2., 0., 1.]) + r).c, p.c);
+    }
+
+    #[test]
+    fn deflation() {
+        let p = Polynomial::new([1., 5., -14., 0.]);
+        let (q, r) = p.deflate(-7.);
+        assert_eq!(q.c, [1., -2., 0.]);
+        assert_eq!(r, 0.);
+
+        // A non-root leaves the polynomial value as the remainder
+        let (_, r) = p.deflate(4.);
+        assert_eq!(r, 88.);
+
+        // (x^2 + 1)(x - 3) deflated by the conjugate pair ±i
+        let p = Polynomial::new([1., -3., 1., -3.]);
+        let (q, r) = p.deflate_conjugate(0., 1.);
+        assert_eq!(q.c, [1., -3.]);
+        assert_eq!(r.c, [0., 0.]);
+
+        // (x^2 - 2x + 5)(x^2 + 4) deflated by 1 ± 2i
+        let p = Polynomial::new([1., -2., 9., -8., 20.]);
+        let (q, r) = p.deflate_conjugate(1., 2.);
+        assert_eq!(q.c, [1., 0., 4.]);
+        assert_eq!(r.c, [0., 0.]);
+    }
+
+    #[test]
+    fn gcd() {
+        let tol = 1e-10;
+
+        // gcd((x - 1)(x - 2)(x + 4), (x - 1)(x + 3)) = x - 1
+        let p = Polynomial::new([1., 1., -10., 8.]);
+        let q = Polynomial::new([1., 2., -3.]);
+        let g = p.gcd(&q, tol);
+        assert_eq!(g.c.len(), 4);
+        assert_eq!(g.c[0], 0.);
+        assert_eq!(g.c[1], 0.);
+        assert!((g.c[2] - 1.).abs() < tol);
+        assert!((g.c[3] + 1.).abs() < tol);
+
+        // Coprime polynomials have a constant divisor
+        let g = q.gcd(&Polynomial::new([1., 5.]), tol);
+        assert_eq!(g.c, [0., 0., 1.]);
+
+        // gcd(p, 0) = p, made monic
+        let g = Polynomial::new([2., -4.]).gcd(&Polynomial::new([0.]), tol);
+        assert_eq!(g.c, [1., -2.]);
+
+        // A perturbed common root is still detected with a loose tolerance
+        let r = Polynomial::new([1., -(1. + 1e-9)]) * Polynomial::new([1., 4.]);
+        let g = r.gcd(&q, 1e-6);
+        assert_eq!(g.c[0], 0.);
+        assert!((g.c[2] + 1.).abs() < 1e-6);
+        let g = r.gcd(&q, 1e-12);
+        assert_eq!(g.c, [0., 0., 1.]);
+    }
+
+    #[test]
+    fn square_free() {
+        let tol = 1e-9;
+
+        // (x - 1)^2 (x + 2) = x^3 - 3x + 2
+        let p = Polynomial::new([1., 0., -3., 2.]);
+        let f = p.square_free(tol);
+        assert!((f[0].c[2] - 1.).abs() < tol && (f[0].c[3] - 2.).abs() < tol);
+        assert!((f[1].c[2] - 1.).abs() < tol && (f[1].c[3] + 1.).abs() < tol);
+        assert_eq!(f[2].c, [0., 0., 0., 1.]);
+
+        // (x - 3)^3 (x^2 + 1) has a square-free part of x^2 + 1
+        let q = Polynomial::new([1., -9., 27., -27.]) * Polynomial::new([1., 0., 1.]);
+        let f = q.square_free(tol);
+        for (i, k) in [0., 0., 0., 1., 0., 1.].iter().enumerate() {
+            assert!((f[0].c[i] - k).abs() < tol);
+        }
+        assert_eq!(f[1].c, [0., 0., 0., 0., 0., 1.]);
+        for (i, k) in [0., 0., 0., 0., 1., -3.].iter().enumerate() {
+            assert!((f[2].c[i] - k).abs() < tol);
+        }
+        assert_eq!(f[3].c, [0., 0., 0., 0., 0., 1.]);
+    }
+
+    #[test]
+    fn resultant() {
+        // (x - 1)(x - 2) and 3(x - 3) give 3^2 (1 - 3)(2 - 3)
+        let p = Polynomial::new([1., -3., 2.]);
+        let q = Polynomial::new([3., -9.]);
+        assert_eq!(super::resultant(&p, &q), 18.);
+        assert_eq!(q.resultant(&p), 18.);
+
+        // A shared root, and leading zeros, which don't count
+        let q = Polynomial::new([0., 0., 1., -2.]);
+        assert_eq!(p.resultant(&q), 0.);
+        assert_eq!(p.resultant(&Polynomial::new([0., 5.])), 25.);
+        assert_eq!(p.resultant(&Polynomial::new([0., 0.])), 0.);
+
+        // Eliminating y from x^2 + y^2 - 25 and y - 3 leaves x^2 - 16, as the
+        // resultant of the polynomials in y at each x
+        let at =
+            |x: f64| Polynomial::new([1., 0., x * x - 25.]).resultant(&Polynomial::new([1., -3.]));
+        for x in [-4., 0., 1.5, 4., 7.] {
+            assert_eq!(at(x), x * x - 16.);
+        }
+
+        // Exact for integer coefficients of degree 4 and 3, whose minors stay
+        // well below 2^53
+        let p = Polynomial::new([3., -7., 2., -11., 5.]);
+        let q = Polynomial::new([2., -9., 4., -6.]);
+        assert_eq!(p.resultant(&q), 421_043.);
+    }
+
+    #[test]
+    fn discriminant() {
+        assert_eq!(Polynomial::new([2., 3., -5.]).discriminant(), 49.);
+        assert_eq!(Polynomial::new([1., 0., 1.]).discriminant(), -4.);
+        assert_eq!(Polynomial::new([1., -6., 9.]).discriminant(), 0.);
+
+        // b²c² - 4ac³ - 4b³d - 27a²d² + 18abcd
+        let [a, b, c, d] = [2., -3., -11., 6.];
+        let x = Polynomial::new([a, b, c, d]);
+        let expected =
+            b * b * c * c - 4. * a * c * c * c - 4. * b * b * b * d - 27. * a * a * d * d
+                + 18. * a * b * c * d;
+        assert_eq!(x.discriminant(), expected);
+        assert!(x.discriminant() > 0.);
+        assert!(Polynomial::new([1., 0., 0., 1.]).discriminant() < 0.);
+
+        // (x + 3)(x + 1)(x - 2)(x - 5), whose discriminant is the product of
+        // the squared differences of its roots
+        let x = Polynomial::new([1., -3., -15., 19., 30.]);
+        let product: f64 = 2. * 5. * 8. * 3. * 6. * 3.;
+        assert_eq!(x.discriminant(), product * product);
+
+        // (x^2 - 3)^2 has double roots at ±√3, found exactly
+        let x = Polynomial::new([1., -2., 1.]).compose(&Polynomial::new([1., 0., -2.]));
+        assert_eq!(x.discriminant(), 0.);
+
+        // Leading zeros don't count, and low degrees
+        assert_eq!(Polynomial::new([0., 2., 3., -5.]).discriminant(), 49.);
+        assert_eq!(Polynomial::new([0., 4., 1.]).discriminant(), 1.);
+        assert_eq!(Polynomial::new([0., 0., 3.]).discriminant(), 0.);
+    }
+
+    #[test]
+    fn calculus() {
+        let p = Polynomial::new([1., 5., -14., 0.]);
+        assert_eq!(p.derivative().c, [3., 10., -14.]);
+        assert_eq!(p.derivative().derivative().c, [6., 10.]);
+        assert_eq!(Polynomial::new([7.]).derivative().c, []);
+
+        let q = Polynomial::new([3., 10., -14.]);
+        assert_eq!(q.antiderivative(2.).c, [1., 5., -14., 2.]);
+        assert_eq!(q.antiderivative(0.).derivative().c, q.c);
+
+        // Integral of x^2 over [0, 3] is 9
+        assert_eq!(Polynomial::new([1., 0., 0.]).integrate(0., 3.), 9.);
+        assert_eq!(q.integrate(-1., 2.), p.eval(2.) - p.eval(-1.));
+
+        let d = p.eval_with_derivatives::<4>(2.);
+        assert_eq!(d, [0., 18., 22., 6., 0.]);
+        let d = p.eval_with_derivatives::<0>(4.);
+        assert_eq!(d, [88.]);
+
+        // (x - 1)^7 has every derivative below the seventh vanish at 1
+        let r = Polynomial::new([1., -7., 21., -35., 35., -21., 7., -1.]);
+        let d = r.eval_with_derivatives::<7>(1.);
+        assert_eq!(d, [0., 0., 0., 0., 0., 0., 0., 5040.]);
+        for x in [-2., 0.5, 3.] {
+            let d = r.eval_with_derivatives::<2>(x);
+            assert_eq!(d[0], r.eval(x));
+            assert_eq!(d[1], r.derivative().eval(x));
+            assert_eq!(d[2], r.derivative().derivative().eval(x));
+        }
+    }
+
     #[test]
     fn roots_0() {
         let tol = 1e-7;
 
         // A nonzero constant polynomial
         let x = Polynomial::from([1.]);
-        let r = x.root_constant(tol);
-        // assert_eq!(r[0].is_nan(), true);
-        assert_eq!(r.len(), 1);
+        let r = x.roots(tol);
+        assert!(r.is_empty());
+        assert!(!r.failed());
 
-        // Additive identity cast to a polynomial
+        // Additive identity cast to a polynomial, every x is a root
         let y = Polynomial::from([0.]);
-        let s = y.root_constant(tol);
-        assert_eq!(s[0], 0.);
-        assert_eq!(s.len(), 1);
+        let s = y.roots(tol);
+        assert!(s.is_empty());
+        assert!(s.failed());
     }
 
     #[test]
@@ -199,26 +1109,26 @@ mod tests {
         let x = Polynomial::new([0., 1., 1.]);
         let r = x.roots(tol);
         assert_eq!(r[0], -1.); // check first root
-                               // assert_eq!(r[1].is_finite(), false); // check second root
-        assert_eq!(r.len(), 2); // check array length
+        assert_eq!(r.len(), 1); // the second root is at infinity
 
         // Quadratic p(x) = x^2 - x - 12 with roots 4,-3
         let x = Polynomial::new([1., -1., -12.]);
         let r = x.roots(tol);
-        assert_eq!(r[0], 4.);
-        assert_eq!(r[1], -3.);
+        assert_eq!(r[0], -3.);
+        assert_eq!(r[1], 4.);
 
         // Quadratic p(x) = x^2 - 6x + 9 with root x = 3 with multiplicity 2
         let x = Polynomial::new([1., -6., 9.]);
         let r = x.roots(tol);
-        assert_eq!(r[0], 3.);
-        assert_eq!(r[1], 3.);
+        assert_eq!(r, [3.]);
+        assert_eq!(r.multiplicity(0), 2);
+        assert_eq!(r.count(), 2);
 
         // Quadratic p(x) = x^2 - 3x + 5 with complex roots
         let x = Polynomial::new([1., -3., 5.]);
         let r = x.roots(tol);
-        assert_eq!(r[0].is_nan(), true);
-        assert_eq!(r[1].is_nan(), true);
+        assert!(r.is_empty());
+        assert!(!r.failed());
     }
 
     #[test]
@@ -226,9 +1136,8 @@ mod tests {
         // p(x) = 0x^2 + 1x + 1 with root -1
         let x = Polynomial::new([0., 1., 1.]);
         let r = solvers::yuksel::roots_quadratic(&x);
-        assert_eq!(r[1], -1.);
-        assert_eq!(r[0].is_finite(), false);
-        assert_eq!(r.len(), 2);
+        assert_eq!(r[0], -1.);
+        assert_eq!(r.len(), 1);
 
         // Quadratic p(x) = x^2 - x - 12 with roots 4,-3
         let x = Polynomial::new([1., -1., -12.]);
@@ -240,13 +1149,13 @@ mod tests {
         let x = Polynomial::new([1., -6., 9.]);
         let r = solvers::yuksel::roots_quadratic(&x);
         assert_eq!(r[0], 3.);
-        assert_eq!(r[1].is_nan(), true);
+        assert_eq!(r.len(), 1);
+        assert_eq!(r.multiplicity(0), 2);
 
         // Quadratic p(x) = x^2 - 3x + 5 with complex roots
         let x = Polynomial::new([1., -3., 5.]);
         let r = solvers::yuksel::roots_quadratic(&x);
-        assert_eq!(r[0].is_nan(), true);
-        assert_eq!(r[1].is_nan(), true);
+        assert!(r.is_empty());
     }
 
     #[test]
@@ -269,12 +1178,189 @@ mod tests {
         // Cubic p(x) = 1x^3 + 5x^2 + -14x + 0 with roots -7, 0, 2
         let x = Polynomial::new([1., 5., -14., 0.]);
         let r = solvers::blinn::Blinn::roots_cubic(&x);
-        assert_eq!((r[0] - 2.0).abs() < 5.0 * tol, true); // check first root
-        assert_eq!((r[1] - 0.0).abs() < 5.0 * tol, true); // check second root
-        assert_eq!((r[2] + 7.0).abs() < 5.0 * tol, true); // check third root
+        assert!((r[0] + 7.0).abs() < 5.0 * tol); // check first root
+        assert!((r[1] - 0.0).abs() < 5.0 * tol); // check second root
+        assert!((r[2] - 2.0).abs() < 5.0 * tol); // check third root
         assert_eq!(r.len(), 3); // check array length
     }
 
+    #[test]
+    fn roots_4_blinn() {
+        let tol = 1e-12;
+
+        // (x + 3)(x + 1)(x - 2)(x - 5) = x^4 - 3x^3 - 15x^2 + 19x + 30
+        let x = Polynomial::new([1., -3., -15., 19., 30.]);
+        let r = solvers::blinn::Blinn::roots_quartic(&x);
+        assert_eq!(r.len(), 4);
+        for (a, b) in r.iter().zip([-3., -1., 2., 5.]) {
+            assert!((a - b).abs() < tol);
+        }
+        assert!(solvers::analysis::is_backward_stable(
+            &x,
+            &r,
+            8. * f64::EPSILON
+        ));
+
+        // (x^2 + 1)(x - 4)(x + 0.5), with only two real roots
+        let x = Polynomial::new([1., 0., 1.]) * Polynomial::new([1., -3.5, -2.]);
+        let r = solvers::blinn::Blinn::roots_quartic(&x);
+        assert_eq!(r.len(), 2);
+        assert!((r[0] + 0.5).abs() < tol);
+        assert!((r[1] - 4.).abs() < tol);
+
+        // Biquadratic x^4 - 5x^2 + 4 with roots ±1, ±2
+        let x = Polynomial::new([1., 0., -5., 0., 4.]);
+        let r = solvers::blinn::Blinn::roots_quartic(&x);
+        assert_eq!(r, [-2., -1., 1., 2.]);
+
+        // (x^2 - 1)(x^2 + 4) + 10^-9 x, nearly biquadratic, has its real roots
+        // moved off ±1 by 10^-10, to first order, where the resolvent root
+        // is too small to resolve
+        let x = Polynomial::new([1., 0., 3., 1e-9, -4.]);
+        let r = solvers::blinn::Blinn::roots_quartic(&x);
+        assert_eq!(r.len(), 2);
+        assert!(!r.failed());
+        for (a, b) in r.iter().zip([-1. - 1e-10, 1. - 1e-10]) {
+            assert!((a - b).abs() < 1e-15);
+        }
+
+        // x^4 + 1 has no real roots
+        let r = solvers::blinn::Blinn::roots_quartic(&Polynomial::new([1., 0., 0., 0., 1.]));
+        assert!(r.is_empty());
+
+        // A vanishing leading coefficient falls back on the cubic
+        let x = Polynomial::new([0., 1., 5., -14., 0.]);
+        let r = solvers::blinn::Blinn::roots_quartic(&x);
+        assert_eq!(r.len(), 3);
+        for (a, b) in r.iter().zip([-7., 0., 2.]) {
+            assert!((a - b).abs() < tol);
+        }
+
+        // f32 coefficients
+        let x = Polynomial::new([1.0_f32, -3.0, -15.0, 19.0, 30.0]);
+        let r = solvers::blinn::Blinn::roots_quartic(&x);
+        for (a, b) in r.iter().zip([-3.0, -1.0, 2.0, 5.0]) {
+            assert!((a - b).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn roots_homogeneous() {
+        use solvers::blinn::Blinn;
+        let tol = 1e-12;
+        let value = |[x, w]: [f64; 2]| x / w;
+
+        // 0x^2 + 2x - 4 has a root at infinity in place of dividing by zero
+        let r = Blinn::roots_quadratic_homogeneous(0., 2., -4.);
+        assert_eq!(r.len(), 2);
+        assert_eq!(value(r[0]), 2.);
+        assert_eq!(r[1][1], 0.);
+        assert_eq!(r.at_infinity(), 1);
+        assert_eq!(r.affine(), [2.]);
+
+        // A constant is a double root at infinity, and zero is no polynomial
+        let r = Blinn::roots_homogeneous(&Polynomial::new([0., 0., 3.]));
+        assert_eq!(r.at_infinity(), 2);
+        assert_eq!(r.len(), 1);
+        assert!(Blinn::roots_homogeneous(&Polynomial::new([0., 0., 0.])).failed());
+        assert!(Blinn::roots_quadratic_homogeneous(1., 0., 1.).is_empty());
+
+        // Cubic x^3 + 5x^2 - 14x with roots -7, 0 and 2
+        let r = Blinn::roots_homogeneous(&Polynomial::new([1., 5., -14., 0.]));
+        assert_eq!(r.count(), 3);
+        assert_eq!(r.at_infinity(), 0);
+        for (&a, b) in r.iter().zip([-7., 0., 2.]) {
+            assert!((value(a) - b).abs() < tol);
+        }
+
+        // The same with its leading coefficient dropped
+        let r = Blinn::roots_cubic_homogeneous(&Polynomial::new([0., 5., -14., 0.]));
+        assert_eq!(r[0], [0., 1.]);
+        assert!((value(r[1]) - 2.8).abs() < tol);
+        assert_eq!(r.at_infinity(), 1);
+
+        // A root near infinity comes from the reversed chart, as 1 / w
+        let r = Blinn::roots_cubic_homogeneous(&Polynomial::new([1e-20, -1., 0., 1.]));
+        assert_eq!(r.len(), 3);
+        assert!((r[0][1] - 1.).abs() < tol && (value(r[0]) + 1.).abs() < tol);
+        assert!((r[2][0] - 1.).abs() < tol && (r[2][1] - 1e-20).abs() < 1e-32);
+
+        // Quartics, with one and two roots at infinity
+        let x = Polynomial::new([0., 1., -3., -15., 19.]);
+        let r = Blinn::roots_quartic_homogeneous(&x);
+        assert_eq!(r.at_infinity(), 1);
+        assert_eq!(r.count(), 4);
+        let r = Blinn::roots_homogeneous(&Polynomial::new([0., 0., 1., 0., -4.]));
+        assert_eq!(r.at_infinity(), 2);
+        assert_eq!(r.affine(), [-2., 2.]);
+        let r = Blinn::roots_homogeneous(&Polynomial::new([1., -3., -15., 19., 30.]));
+        for (&a, b) in r.iter().zip([-3., -1., 2., 5.]) {
+            assert!((value(a) - b).abs() < tol);
+        }
+
+        // Blinn has no closed form past the quartic
+        assert!(Blinn::roots_homogeneous(&Polynomial::new([1.; 6])).failed());
+    }
+
+    #[test]
+    fn roots_with() {
+        use solvers::{blinn::Blinn, yuksel::Yuksel};
+        let tol = 1e-12;
+
+        // Cubic p(x) = 1x^3 + 5x^2 + -14x + 0 with roots -7, 0, 2
+        let x = Polynomial::new([1., 5., -14., 0.]);
+        let a = x.roots_with::<Blinn>(tol);
+        let b = x.roots_with::<Yuksel>(tol);
+        assert_eq!(a.len(), 3);
+        assert_eq!(b, [-7., 0., 2.]);
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((a - b).abs() < tol);
+        }
+
+        // (x + 3)(x + 1)(x - 2)(x - 5)
+        let x = Polynomial::new([1., -3., -15., 19., 30.]);
+        let a = x.roots_with::<Blinn>(tol);
+        let b = x.roots_with::<Yuksel>(tol);
+        assert_eq!(a.len(), 4);
+        for (a, b) in a.iter().zip(b.iter()) {
+            assert!((a - b).abs() < tol);
+        }
+
+        // Blinn has no closed form past the quartic
+        let x = Polynomial::new([1., 0., 0., 0., 1., -34.]);
+        assert!(x.roots_with::<Blinn>(tol).failed());
+        assert_eq!(x.roots_with::<Yuksel>(tol).len(), 1);
+
+        // Linear polynomials
+        let x = Polynomial::new([2., -1.]);
+        assert_eq!(x.roots_with::<Blinn>(tol), [0.5]);
+        assert_eq!(x.roots_with::<Yuksel>(tol), [0.5]);
+    }
+
+    #[test]
+    fn fit() {
+        // A calibration curve y = 0.02x^2 - 1.5x + 40 with alternating noise
+        let xs = [10., 20., 30., 40., 50., 60.];
+        let ys = xs.map(|x| 0.02 * x * x - 1.5 * x + 40.);
+        let noisy: Vec<f64> = ys
+            .iter()
+            .zip([1., -1., 1., -1., 1., -1.])
+            .map(|(y, e)| y + 1e-3 * e)
+            .collect();
+        let f = Polynomial::<f64, 3>::fit(&xs, &noisy).unwrap();
+        assert!((f.polynomial.c[0] - 0.02).abs() < 1e-5);
+        assert!(f.residual > 0. && f.residual < 6e-3);
+        let f = Polynomial::<f64, 3>::fit_weighted(&xs, &ys, &[1.; 6]).unwrap();
+        assert!(f.max_residual < 1e-12);
+    }
+
+    #[test]
+    fn interpolate() {
+        let x = Polynomial::interpolate(&[0., 1., 2.], &[1., 0., 3.]).unwrap();
+        assert_eq!(x, Polynomial::new([2., -3., 1.]));
+        assert!(Polynomial::interpolate(&[0., 0.], &[1., 2.]).is_none());
+    }
+
     #[test]
     fn roots_3_yuksel() {
         let tol = f64::EPSILON;
@@ -285,7 +1371,6 @@ mod tests {
         assert_eq!(r[0], -7.0); // check first root
         assert_eq!(r[1], 0.); // check second root
         assert_eq!(r[2], 2.0); // check third root
-
-        // assert_eq!(r.len(), 3); // check array length // Clean up solvers with const generics...
+        assert_eq!(r.len(), 3); // check root count
     }
 }
@@ -0,0 +1,425 @@
+//! Parsing and rendering of polynomials as text.
+//!
+//! Renderings fold signs, drop zero terms and leave unit coefficients and
+//! powers implicit, in a style chosen by the formatter flags:
+//!
+//! - `{}`, compact, as in `x^3 + 5x^2 - 14x`, which parses back exactly
+//! - `{:#}`, with Unicode superscripts, as in `x³ + 5x² − 14x`
+//! - `{:+}`, LaTeX, as in `x^{3} + 5x^{2} - 14x`
+//!
+//! `p.unicode()` and `p.latex()` render the latter two where no flag can be
+//! given, as with to_string. A precision applies to every coefficient, as in
+//! `{:.2}`.
+//!
+//! Parsing accepts the compact and Unicode forms, and more generally a sum of
+//! terms each with an optional coefficient, an optional multiplication sign
+//! `*`, `×` or `·`, and the variable with an optional power, as in
+//! `-x^3 + 2.5 * x - 1e-3`. Whitespace is ignored, any single letter may be
+//! the variable, and repeated powers are summed.
+
+use core::{fmt, str::FromStr};
+
+use crate::{float::Float, number::Number, polynomial::Polynomial};
+
+const SUPERSCRIPTS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+
+/// A rendering that folds signs and drops zero terms.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Style {
+    Compact,
+    Unicode,
+    Latex,
+}
+
+impl Style {
+    fn minus(self) -> &'static str {
+        match self {
+            Style::Unicode => "−",
+            _ => "-",
+        }
+    }
+
+    fn power(self, f: &mut fmt::Formatter<'_>, k: usize) -> fmt::Result {
+        match (self, k) {
+            (_, 0) => Ok(()),
+            (_, 1) => f.write_str("x"),
+            (Style::Compact, _) => write!(f, "x^{}", k),
+            (Style::Latex, _) => write!(f, "x^{{{}}}", k),
+            (Style::Unicode, _) => {
+                f.write_str("x")?;
+                let digits = k.to_string();
+                for d in digits.bytes() {
+                    write!(f, "{}", SUPERSCRIPTS[(d - b'0') as usize])?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Renders the polynomial in a style, folding signs and dropping zero terms.
+fn render<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    style: Style,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result {
+    let mut first = true;
+    for (i, &k) in p.c.iter().enumerate() {
+        if k == T::ZERO {
+            continue;
+        }
+        let power = N - 1 - i;
+        let negative = k < T::ZERO;
+        let magnitude = if negative { -k } else { k };
+        if first {
+            if negative {
+                f.write_str(style.minus())?;
+            }
+        } else if negative {
+            write!(f, " {} ", style.minus())?;
+        } else {
+            f.write_str(" + ")?;
+        }
+        first = false;
+
+        // Unit coefficients are implicit, and compound ones, as of complex
+        // polynomials, are parenthesised
+        if magnitude != T::ONE || power == 0 {
+            let s = match f.precision() {
+                Some(precision) => format!("{:.*}", precision, magnitude),
+                None => format!("{}", magnitude),
+            };
+            if s.contains(' ') {
+                write!(f, "({})", s)?;
+            } else {
+                f.write_str(&s)?;
+            }
+        }
+        style.power(f, power)?;
+    }
+    if first {
+        f.write_str("0")?;
+    }
+    Ok(())
+}
+
+/// Renders the polynomial in the compact form, with `{:#}` in Unicode or with
+/// `{:+}` as LaTeX, as described by the module.
+impl<T: Number<Type = T>, const N: usize> fmt::Display for Polynomial<T, N>
+where
+    T: Float,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let style = if f.sign_plus() {
+            Style::Latex
+        } else if f.alternate() {
+            Style::Unicode
+        } else {
+            Style::Compact
+        };
+        render(self, style, f)
+    }
+}
+
+/// Renders a polynomial with Unicode superscripts, as returned by
+/// `Polynomial::unicode`.
+#[derive(Copy, Clone, Debug)]
+pub struct Unicode<'a, T: Float, const N: usize>(&'a Polynomial<T, N>);
+
+/// Renders a polynomial as LaTeX, as returned by `Polynomial::latex`.
+#[derive(Copy, Clone, Debug)]
+pub struct Latex<'a, T: Float, const N: usize>(&'a Polynomial<T, N>);
+
+impl<T: Number<Type = T>, const N: usize> Polynomial<T, N>
+where
+    T: Float,
+{
+    /// Displays with Unicode superscripts and minus signs, as `{:#}` does, as
+    /// in `x³ + 5x² − 14x`.
+    #[inline]
+    pub fn unicode(&self) -> Unicode<'_, T, N> {
+        Unicode(self)
+    }
+
+    /// Displays as LaTeX, as `{:+}` does, as in `x^{3} + 5x^{2} - 14x`.
+    #[inline]
+    pub fn latex(&self) -> Latex<'_, T, N> {
+        Latex(self)
+    }
+}
+
+impl<T: Float, const N: usize> fmt::Display for Unicode<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self.0, Style::Unicode, f)
+    }
+}
+
+impl<T: Float, const N: usize> fmt::Display for Latex<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        render(self.0, Style::Latex, f)
+    }
+}
+
+/// Why a polynomial failed to parse, with the byte offset of the fault where
+/// there is one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParsePolynomialError {
+    /// The input holds no terms.
+    Empty,
+    /// A character that can't appear where it does.
+    Unexpected(usize, char),
+    /// The input ended in the middle of a term.
+    UnexpectedEnd,
+    /// A coefficient or power that isn't a valid number, or a coefficient
+    /// too large to be finite.
+    InvalidNumber(usize),
+    /// A second variable, where the first was another letter.
+    Variable(usize, char),
+    /// A term whose power exceeds the degree of the polynomial.
+    Degree(usize, usize),
+}
+
+impl fmt::Display for ParsePolynomialError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Empty => f.write_str("empty polynomial"),
+            Self::Unexpected(i, c) => write!(f, "unexpected '{}' at {}", c, i),
+            Self::UnexpectedEnd => f.write_str("unexpected end of polynomial"),
+            Self::InvalidNumber(i) => write!(f, "invalid number at {}", i),
+            Self::Variable(i, c) => write!(f, "second variable '{}' at {}", c, i),
+            Self::Degree(i, k) => write!(f, "power {} at {} exceeds the degree", k, i),
+        }
+    }
+}
+
+impl std::error::Error for ParsePolynomialError {}
+
+/// A cursor over the input, skipping whitespace.
+struct Parser<'a> {
+    s: &'a str,
+    i: usize,
+    variable: Option<char>,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&mut self) -> Option<char> {
+        let rest = &self.s[self.i..];
+        let trimmed = rest.trim_start();
+        self.i += rest.len() - trimmed.len();
+        trimmed.chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.i += c.len_utf8();
+        Some(c)
+    }
+
+    /// Takes the longest prefix matching the predicate, without skipping
+    /// whitespace inside it.
+    fn take_while(&mut self, mut f: impl FnMut(usize, char) -> bool) -> &'a str {
+        self.peek();
+        let start = self.i;
+        let end = self.s[start..]
+            .char_indices()
+            .find(|&(j, c)| !f(j, c))
+            .map_or(self.s.len(), |(j, _)| start + j);
+        self.i = end;
+        &self.s[start..end]
+    }
+
+    /// A finite decimal coefficient, with an optional fraction and exponent.
+    fn number<T: Float + FromStr>(&mut self) -> Result<Option<T>, ParsePolynomialError> {
+        self.peek();
+        let start = self.i;
+        let bytes = self.s.as_bytes();
+        let mut exponent = false;
+        let text = self.take_while(|j, c| {
+            let at = start + j;
+            if c.is_ascii_digit() || c == '.' {
+                return true;
+            }
+            // An exponent needs a digit after it, or else e is the variable
+            if (c == 'e' || c == 'E') && !exponent {
+                let next = bytes.get(at + 1).copied();
+                let after = bytes.get(at + 2).copied();
+                let digit = |b: Option<u8>| b.is_some_and(|b| b.is_ascii_digit());
+                if digit(next) || (matches!(next, Some(b'+' | b'-')) && digit(after)) {
+                    exponent = true;
+                    return true;
+                }
+                return false;
+            }
+            (c == '+' || c == '-') && exponent && matches!(bytes[at - 1], b'e' | b'E')
+        });
+        if text.is_empty() {
+            return Ok(None);
+        }
+        match text.parse::<T>() {
+            Ok(k) if k.is_finite() => Ok(Some(k)),
+            _ => Err(ParsePolynomialError::InvalidNumber(start)),
+        }
+    }
+
+    /// A power, as ^k or in superscript digits.
+    fn power(&mut self) -> Result<usize, ParsePolynomialError> {
+        if self.peek() == Some('^') {
+            self.bump();
+            let start = self.i;
+            let digits = self.take_while(|_, c| c.is_ascii_digit());
+            return digits
+                .parse()
+                .map_err(|_| ParsePolynomialError::InvalidNumber(start));
+        }
+        let start = self.i;
+        let digits = self.take_while(|_, c| SUPERSCRIPTS.contains(&c));
+        if digits.is_empty() {
+            return Ok(1);
+        }
+        digits
+            .chars()
+            .map(|c| SUPERSCRIPTS.iter().position(|&s| s == c).unwrap())
+            .try_fold(0usize, |acc, d| acc.checked_mul(10)?.checked_add(d))
+            .ok_or(ParsePolynomialError::InvalidNumber(start))
+    }
+
+    /// A term, as its coefficient and power.
+    fn term<T: Float + FromStr>(&mut self) -> Result<(T, usize), ParsePolynomialError> {
+        let start = self.i;
+        let coefficient = self.number::<T>()?;
+        if matches!(self.peek(), Some('*' | '×' | '·')) {
+            if coefficient.is_none() {
+                return Err(ParsePolynomialError::Unexpected(
+                    self.i,
+                    self.bump().unwrap(),
+                ));
+            }
+            self.bump();
+            if !self.peek().is_some_and(|c| c.is_alphabetic()) {
+                return match self.peek() {
+                    Some(c) => Err(ParsePolynomialError::Unexpected(self.i, c)),
+                    None => Err(ParsePolynomialError::UnexpectedEnd),
+                };
+            }
+        }
+        match self.peek() {
+            Some(c) if c.is_alphabetic() => {
+                let at = self.i;
+                match self.variable {
+                    Some(v) if v != c => return Err(ParsePolynomialError::Variable(at, c)),
+                    _ => self.variable = Some(c),
+                }
+                self.bump();
+                Ok((coefficient.unwrap_or(T::ONE), self.power()?))
+            }
+            _ => match coefficient {
+                Some(k) => Ok((k, 0)),
+                None => match self.peek() {
+                    Some(c) => Err(ParsePolynomialError::Unexpected(self.i, c)),
+                    None if self.i == start && start == 0 => Err(ParsePolynomialError::Empty),
+                    None => Err(ParsePolynomialError::UnexpectedEnd),
+                },
+            },
+        }
+    }
+}
+
+/// Parses a polynomial as described by the module, failing if it has terms
+/// of too high a power. Coefficients are parsed with the FromStr of T, so
+/// only real polynomials are supported.
+impl<T: Number<Type = T>, const N: usize> FromStr for Polynomial<T, N>
+where
+    T: Float + FromStr,
+    [(); N]:,
+{
+    type Err = ParsePolynomialError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            s,
+            i: 0,
+            variable: None,
+        };
+        let mut c = [T::ZERO; N];
+        let mut negative = false;
+        if let Some(sign @ ('+' | '-' | '−')) = parser.peek() {
+            parser.bump();
+            negative = sign != '+';
+        }
+        loop {
+            let at = parser.i;
+            let (k, power) = parser.term::<T>()?;
+            if power >= N {
+                return Err(ParsePolynomialError::Degree(at, power));
+            }
+            c[N - 1 - power] += if negative { -k } else { k };
+            negative = match parser.bump() {
+                None => break,
+                Some('+') => false,
+                Some('-' | '−') => true,
+                Some(ch) => {
+                    let at = parser.i - ch.len_utf8();
+                    return Err(ParsePolynomialError::Unexpected(at, ch));
+                }
+            };
+        }
+        Ok(Polynomial::new(c))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rendering() {
+        let p = Polynomial::new([1., 5., -14., 0.]);
+        assert_eq!(format!("{}", p), "x^3 + 5x^2 - 14x");
+        assert_eq!(format!("{:#}", p), "x³ + 5x² − 14x");
+        assert_eq!(format!("{:+}", p), "x^{3} + 5x^{2} - 14x");
+        assert_eq!(p.unicode().to_string(), format!("{:#}", p));
+        assert_eq!(p.latex().to_string(), format!("{:+}", p));
+
+        let p = Polynomial::new([-1., 0., 0., 0., 0., 0., 0., 0., 0., 0., 0.5, -1.]);
+        assert_eq!(format!("{}", p), "-x^11 + 0.5x - 1");
+        assert_eq!(format!("{:#}", p), "−x¹¹ + 0.5x − 1");
+        assert_eq!(format!("{:+}", p), "-x^{11} + 0.5x - 1");
+        assert_eq!(format!("{:.2}", p), "-x^11 + 0.50x - 1.00");
+        assert_eq!(format!("{:+.1}", p), "-x^{11} + 0.5x - 1.0");
+        assert_eq!(format!("{}", Polynomial::new([0., 0.])), "0");
+    }
+
+    #[test]
+    fn parsing() {
+        let p: Polynomial<f64, 4> = "x^3 + 5x^2 - 14x".parse().unwrap();
+        assert_eq!(p, Polynomial::new([1., 5., -14., 0.]));
+        let p: Polynomial<f64, 4> = " - 2 * t^2+t -0.5e1 + 3t^2".parse().unwrap();
+        assert_eq!(p, Polynomial::new([0., 1., 1., -5.]));
+        let p: Polynomial<f64, 3> = "x² − 2.5e-1·x".parse().unwrap();
+        assert_eq!(p, Polynomial::new([1., -0.25, 0.]));
+        let p: Polynomial<f64, 2> = "7".parse().unwrap();
+        assert_eq!(p, Polynomial::new([0., 7.]));
+
+        // Rendering in the compact form parses back exactly
+        let p = Polynomial::new([0.1, -1. / 3., 0., 1e-300, -2.]);
+        let q: Polynomial<f64, 5> = p.to_string().parse().unwrap();
+        assert_eq!(p, q);
+        let q: Polynomial<f64, 5> = format!("{:#}", p).parse().unwrap();
+        assert_eq!(p, q);
+    }
+
+    #[test]
+    fn errors() {
+        use ParsePolynomialError::*;
+        let parse = |s: &str| s.parse::<Polynomial<f64, 3>>();
+        assert_eq!(parse(""), Err(Empty));
+        assert_eq!(parse("x^3"), Err(Degree(0, 3)));
+        assert_eq!(parse("x + y"), Err(Variable(4, 'y')));
+        assert_eq!(parse("x +"), Err(UnexpectedEnd));
+        assert_eq!(parse("2x $ 1"), Err(Unexpected(3, '$')));
+        assert_eq!(parse("1.2.3x"), Err(InvalidNumber(0)));
+        assert_eq!(parse("2 * + x"), Err(Unexpected(4, '+')));
+        assert_eq!(parse("x^"), Err(InvalidNumber(2)));
+        assert_eq!(parse("x - 1e400"), Err(InvalidNumber(4)));
+    }
+}
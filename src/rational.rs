@@ -0,0 +1,394 @@
+//! Rational functions P(x) / Q(x).
+//!
+//! Transfer functions and Padé approximations are quotients of polynomials,
+//! whose zeros and poles are the roots of the numerator and denominator once
+//! any common factor is cancelled. Over the complex numbers a rational function
+//! splits into a polynomial plus a sum of terms c / (x - p)^k over its poles p,
+//! its partial fraction decomposition, whose coefficients are read off the
+//! Laurent series about each pole.
+
+use crate::{
+    complex::Complex,
+    float::Float,
+    number::Number,
+    polynomial::{derivative_in_place, div_exact, div_rem_in_place, leading, max_len, Polynomial},
+    solvers::{aberth, RootSet},
+};
+
+/// The rational function num(x) / den(x).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Rational<T: Number<Type = T>, const N: usize, const M: usize>
+where
+    T: Float,
+{
+    pub num: Polynomial<T, N>,
+    pub den: Polynomial<T, M>,
+}
+
+/// The term coefficient / (x - pole)^power of a partial fraction
+/// decomposition.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Term<T: Float> {
+    pub pole: Complex<T>,
+    pub power: usize,
+    pub coefficient: Complex<T>,
+}
+
+/// The partial fraction decomposition of a rational function, as a
+/// polynomial, padded with leading zeros, plus a term for each power of each
+/// pole up to its multiplicity.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PartialFractions<T: Float, const N: usize, const M: usize> {
+    pub polynomial: Polynomial<T, N>,
+    terms: [Term<T>; M],
+    len: usize,
+    failed: bool,
+}
+
+impl<T: Float, const N: usize, const M: usize> PartialFractions<T, N, M> {
+    /// The terms, grouped by pole in ascending powers.
+    #[inline]
+    pub fn terms(&self) -> &[Term<T>] {
+        &self.terms[..self.len]
+    }
+
+    /// Whether the poles couldn't all be found, in which case terms may be
+    /// missing.
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
+    /// Evaluates the decomposition at z, which matches the rational function
+    /// away from its poles.
+    pub fn eval(&self, z: Complex<T>) -> Complex<T> {
+        let mut acc = eval_complex(&self.polynomial.c, z);
+        for t in self.terms() {
+            acc += t.coefficient / (z - t.pole).powi(t.power as i32);
+        }
+        acc
+    }
+}
+
+impl<T: Number<Type = T>, const N: usize, const M: usize> Rational<T, N, M>
+where
+    T: Float,
+{
+    pub const fn new(num: Polynomial<T, N>, den: Polynomial<T, M>) -> Self {
+        Self { num, den }
+    }
+
+    /// Evaluates the function at x, which is infinite or NaN at a pole.
+    #[inline]
+    pub fn eval(&self, x: T) -> T {
+        self.num.eval(x) / self.den.eval(x)
+    }
+
+    /// Evaluates the function at the complex point z, as for the frequency
+    /// response of a transfer function along the imaginary axis.
+    pub fn eval_complex<F: Float>(&self, z: Complex<F>) -> Complex<F>
+    where
+        T: Into<Complex<F>>,
+    {
+        eval_complex(&self.num.c, z) / eval_complex(&self.den.c, z)
+    }
+
+    /// Returns the derivative (P'Q - PQ') / Q^2 by the quotient rule, without
+    /// cancelling common factors.
+    pub fn derivative(&self) -> Rational<T, { N + M - 2 }, { 2 * M - 1 }>
+    where
+        [(); N + M - 2]:,
+        [(); 2 * M - 1]:,
+    {
+        let mut dp = [T::ZERO; N];
+        let mut dq = [T::ZERO; M];
+        derivative_in_place(&self.num.c, &mut dp);
+        derivative_in_place(&self.den.c, &mut dq);
+
+        let mut num = [T::ZERO; N + M - 2];
+        let mut qq = [T::ZERO; N + M - 2];
+        convolve(&dp, &self.den.c, &mut num);
+        convolve(&self.num.c, &dq, &mut qq);
+        for (e, k) in num.iter_mut().zip(qq) {
+            *e -= k;
+        }
+        let mut den = [T::ZERO; 2 * M - 1];
+        convolve(&self.den.c, &self.den.c, &mut den);
+        Rational::new(Polynomial::new(num), Polynomial::new(den))
+    }
+
+    /// Cancels the greatest common divisor of the numerator and denominator,
+    /// found to within tol as by Polynomial::gcd, padding each with leading
+    /// zeros. Left as is when the divisor is constant.
+    pub fn cancel(&self, tol: T) -> Self
+    where
+        [(); max_len(N, M)]:,
+    {
+        let g = self.num.gcd(&self.den, tol);
+        if leading(&g.c, tol) + 1 >= max_len(N, M) {
+            return *self;
+        }
+        let mut num = [T::ZERO; N];
+        let mut den = [T::ZERO; M];
+        div_exact(&self.num.c, &g.c, &mut num, tol);
+        div_exact(&self.den.c, &g.c, &mut den, tol);
+        Self::new(Polynomial::new(num), Polynomial::new(den))
+    }
+
+    /// The real zeros, once common factors are cancelled, as found by
+    /// Polynomial::roots.
+    pub fn zeros(&self, tol: T) -> RootSet<T, { N - 1 }>
+    where
+        [(); max_len(N, M)]:,
+    {
+        self.cancel(tol).num.roots(tol)
+    }
+
+    /// The real poles, once common factors are cancelled, as found by
+    /// Polynomial::roots.
+    pub fn poles(&self, tol: T) -> RootSet<T, { M - 1 }>
+    where
+        [(); max_len(N, M)]:,
+    {
+        self.cancel(tol).den.roots(tol)
+    }
+
+    /// Every zero, real and complex, once common factors are cancelled, as
+    /// found by the Aberth–Ehrlich method and padded with NaN, or NaN where
+    /// it fails to converge.
+    pub fn zeros_complex(&self, tol: T) -> [Complex<T>; N - 1]
+    where
+        T: Into<Complex<T>>,
+        [(); max_len(N, M)]:,
+    {
+        aberth::roots(&self.cancel(tol).num, tol)
+    }
+
+    /// Every pole, real and complex, once common factors are cancelled, as
+    /// found by the Aberth–Ehrlich method and padded with NaN, or NaN where
+    /// it fails to converge.
+    pub fn poles_complex(&self, tol: T) -> [Complex<T>; M - 1]
+    where
+        T: Into<Complex<T>>,
+        [(); max_len(N, M)]:,
+    {
+        aberth::roots(&self.cancel(tol).den, tol)
+    }
+
+    /// Decomposes the function into a polynomial plus partial fractions over
+    /// its complex poles, once common factors are cancelled.
+    ///
+    /// Poles are grouped by multiplicity with Polynomial::square_free and
+    /// found by the Aberth–Ehrlich method, a pole it fails to converge on
+    /// being recorded by `failed`. The coefficients of the terms at a pole p
+    /// of multiplicity m are the first m Taylor coefficients of
+    /// (x - p)^m R(x) / Q(x), where R is the remainder of P divided by Q,
+    /// found by dividing the Taylor series of R by that of Q about p. Requires
+    /// a real floating point type.
+    pub fn partial_fractions(&self, tol: T) -> PartialFractions<T, N, M>
+    where
+        T: Into<Complex<T>>,
+        [(); max_len(N, M)]:,
+        [(); M - 1]:,
+    {
+        let r = self.cancel(tol);
+        let zero = Complex::new(T::ZERO, T::ZERO);
+        let mut output = PartialFractions {
+            polynomial: Polynomial::new([T::ZERO; N]),
+            terms: [Term {
+                pole: zero,
+                power: 0,
+                coefficient: zero,
+            }; M],
+            len: 0,
+            failed: false,
+        };
+        let ln = leading(&r.num.c, tol);
+        let ld = leading(&r.den.c, tol);
+        if ld == M {
+            output.failed = true;
+            return output;
+        }
+
+        // P = S Q + R, with S right-aligned in the polynomial part
+        let mut rem = r.num.c;
+        if ln < N && N - ln >= M - ld {
+            div_rem_in_place(&mut rem[ln..], &r.den.c[ld..]);
+            let q = (N - ln) - (M - ld) + 1;
+            output.polynomial.c[N - q..].copy_from_slice(&rem[ln..ln + q]);
+            rem[..ln + q].fill(T::ZERO);
+        }
+
+        let factors = r.den.square_free(tol);
+        for (i, f) in factors.iter().enumerate() {
+            let degree = M - leading(&f.c, T::ZERO) - 1;
+            if degree == 0 {
+                continue;
+            }
+            let m = i + 1;
+            let poles = aberth::roots(f, tol);
+            for &p in poles.iter().take(degree) {
+                if !(p.re.is_finite() && p.im.is_finite()) {
+                    output.failed = true;
+                    continue;
+                }
+                let mut tr = [zero; N];
+                let mut tq = [zero; M];
+                taylor(&rem, p, &mut tr);
+                taylor(&r.den.c, p, &mut tq);
+
+                // g = tr / (tq / (x - p)^m), to order m - 1
+                let mut g = [zero; M];
+                for j in 0..m {
+                    let mut acc = if j < N { tr[j] } else { zero };
+                    for l in 1..=j {
+                        if m + l < M {
+                            acc -= tq[m + l] * g[j - l];
+                        }
+                    }
+                    g[j] = acc / tq[m];
+                }
+                for j in (0..m).rev() {
+                    if output.len == M {
+                        output.failed = true;
+                        break;
+                    }
+                    output.terms[output.len] = Term {
+                        pole: p,
+                        power: m - j,
+                        coefficient: g[j],
+                    };
+                    output.len += 1;
+                }
+            }
+        }
+        output
+    }
+}
+
+/// Evaluates the polynomial c at the complex point z by Horner's scheme.
+fn eval_complex<T: Float + Into<Complex<F>>, F: Float>(c: &[T], z: Complex<F>) -> Complex<F> {
+    c.iter()
+        .fold(Complex::new(F::ZERO, F::ZERO), |acc, &k| acc * z + k.into())
+}
+
+/// Adds the product of a and b into out, all right-aligned, where out may be
+/// shorter than the product if its leading coefficients vanish.
+fn convolve<T: Float>(a: &[T], b: &[T], out: &mut [T]) {
+    let n = out.len();
+    for (i, &x) in a.iter().rev().enumerate() {
+        for (j, &y) in b.iter().rev().enumerate() {
+            if i + j < n {
+                out[n - 1 - i - j] += x * y;
+            }
+        }
+    }
+}
+
+/// Writes the Taylor coefficients of c about p into out, of the same length,
+/// in ascending order, by repeated synthetic division.
+fn taylor<T: Float + Into<Complex<T>>>(c: &[T], p: Complex<T>, out: &mut [Complex<T>]) {
+    let n = c.len();
+    for (e, &k) in out.iter_mut().zip(c) {
+        *e = k.into();
+    }
+    // Each pass leaves the next coefficient at the end of the remaining ones
+    for k in 0..n {
+        for i in 1..n - k {
+            let prev = out[i - 1];
+            out[i] += prev * p;
+        }
+    }
+    out.reverse();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(a: Complex<f64>, b: Complex<f64>, tol: f64) {
+        assert!((a - b).norm() < tol, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn evaluation() {
+        // 1 / (x^2 + 1)
+        let r = Rational::new(Polynomial::new([1.]), Polynomial::new([1., 0., 1.]));
+        assert_eq!(r.eval(1.), 0.5);
+        assert_close(
+            r.eval_complex(Complex::new(0., 2.)),
+            Complex::new(-1. / 3., 0.),
+            1e-15,
+        );
+        assert!(r.poles(1e-12).is_empty());
+        let p = r.poles_complex(1e-14);
+        assert_close(p[0], Complex::new(0., -1.), 1e-12);
+        assert_close(p[1], Complex::new(0., 1.), 1e-12);
+
+        // d/dx x / (x - 1) = -1 / (x - 1)^2
+        let r = Rational::new(Polynomial::new([1., 0.]), Polynomial::new([1., -1.]));
+        let d = r.derivative();
+        assert_eq!(d.eval(3.), -0.25);
+        assert_eq!(d.num.c, [0., -1.]);
+    }
+
+    #[test]
+    fn cancellation() {
+        // (x^2 - 1)(x - 3) / ((x - 1)(x + 2)) = (x + 1)(x - 3) / (x + 2)
+        let r = Rational::new(
+            Polynomial::new([1., 0., -1.]) * Polynomial::new([1., -3.]),
+            Polynomial::new([1., 1., -2.]),
+        );
+        let c = r.cancel(1e-12);
+        assert_eq!(c.den.c[0], 0.);
+        assert!((c.eval(1.) - (-4. / 3.)).abs() < 1e-14);
+        assert!((c.eval(5.) - r.eval(5.)).abs() < 1e-14);
+
+        assert_eq!(r.zeros(1e-12).len(), 2);
+        for (a, b) in r.zeros(1e-12).iter().zip([-1., 3.]) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        let poles = r.poles(1e-12);
+        assert_eq!(poles.len(), 1);
+        assert!((poles[0] + 2.).abs() < 1e-12);
+    }
+
+    #[test]
+    fn partial_fractions() {
+        let tol = 1e-12;
+
+        // (3x + 5) / ((x + 1)(x + 2)) = 2 / (x + 1) + 1 / (x + 2)
+        let r = Rational::new(Polynomial::new([3., 5.]), Polynomial::new([1., 3., 2.]));
+        let f = r.partial_fractions(tol);
+        assert!(!f.failed());
+        assert_eq!(f.terms().len(), 2);
+        for t in f.terms() {
+            assert_eq!(t.power, 1);
+            let c = if t.pole.re < -1.5 { 1. } else { 2. };
+            assert_close(t.coefficient, Complex::new(c, 0.), 1e-12);
+        }
+
+        // x^3 / (x - 1)^2 = x + 2 + 3 / (x - 1) + 1 / (x - 1)^2
+        let r = Rational::new(
+            Polynomial::new([1., 0., 0., 0.]),
+            Polynomial::new([1., -2., 1.]),
+        );
+        let f = r.partial_fractions(tol);
+        assert_eq!(f.polynomial.c, [0., 0., 1., 2.]);
+        assert_eq!(f.terms().len(), 2);
+        assert_eq!(f.terms()[0].power, 1);
+        assert_close(f.terms()[0].coefficient, Complex::new(3., 0.), 1e-9);
+        assert_eq!(f.terms()[1].power, 2);
+        assert_close(f.terms()[1].coefficient, Complex::new(1., 0.), 1e-9);
+
+        // 1 / (x^2 + 1) = (i / 2) / (x + i) - (i / 2) / (x - i)
+        let r = Rational::new(Polynomial::new([1.]), Polynomial::new([1., 0., 1.]));
+        let f = r.partial_fractions(tol);
+        for t in f.terms() {
+            assert_close(t.coefficient, Complex::new(0., -0.5 * t.pole.im), 1e-12);
+        }
+        for z in [Complex::new(0.5, 0.), Complex::new(-2., 0.3)] {
+            assert_close(f.eval(z), r.eval_complex(z), 1e-12);
+        }
+    }
+}
@@ -1,7 +1,24 @@
 // pub mod autodiff;
 // pub mod orellana;
-// pub mod newton;
+pub mod newton;
 pub mod yuksel;
-// pub mod homotopy_tests;
-// pub mod phc;
+pub mod aberth;
+pub mod phc;
 pub mod blinn;
+pub mod analysis;
+pub mod isolation;
+pub mod companion;
+pub mod cluster;
+pub(crate) mod multiset;
+pub mod projective;
+pub mod rootset;
+
+pub use rootset::RootSet;
+
+use crate::{float::Float, polynomial::Polynomial};
+
+/// A strategy for finding the real roots of a polynomial, so that solvers can
+/// be swapped at a call site with Polynomial::roots_with.
+pub trait RootFinder {
+    fn roots<T: Float, const N: usize>(p: &Polynomial<T, N>, tol: T) -> RootSet<T, { N - 1 }>;
+}
@@ -0,0 +1,352 @@
+use crate::{complex::Complex, float::Float, polynomial::Polynomial};
+
+/// Iteration limit for the Aberth–Ehrlich method, which usually converges in
+/// well under a tenth of it.
+const ABERTH_MAX_ITERATIONS: usize = 200;
+
+/// Iteration limit for the Durand–Kerner fallback.
+const DURAND_KERNER_MAX_ITERATIONS: usize = 1000;
+
+#[inline]
+fn zero<F: Float>() -> Complex<F> {
+    Complex::new(F::ZERO, F::ZERO)
+}
+
+/// Evaluates the polynomial and its derivative at z in a single Horner pass.
+#[inline]
+fn eval_with_derivative<F: Float>(c: &[Complex<F>], z: Complex<F>) -> (Complex<F>, Complex<F>) {
+    let mut p = zero();
+    let mut dp = zero();
+    for &k in c.iter() {
+        dp = dp * z + p;
+        p = p * z + k;
+    }
+    (p, dp)
+}
+
+/// Evaluates the polynomial with coefficient magnitudes at |z|, which bounds
+/// the rounding error of evaluating it at z.
+#[inline]
+fn eval_abs<F: Float>(c: &[Complex<F>], z: Complex<F>) -> F {
+    let r = z.norm();
+    c.iter().fold(F::ZERO, |acc, k| acc * r + k.norm())
+}
+
+/// Approximates the positive nth root of v by Newton's method from above.
+fn nth_root<F: Float>(v: F, n: usize) -> F {
+    if n == 1 || v == F::ZERO {
+        return v;
+    }
+    let k = F::from_usize(n);
+    let m = F::from_usize(n - 1);
+    let mut y = F::ONE + v / k;
+    for _ in 0..200 {
+        let next = (m * y + v / y.powi(n as i32 - 1)) / k;
+        if next >= y {
+            break;
+        }
+        y = next;
+    }
+    y
+}
+
+/// Places the initial approximations evenly on a circle enclosing the roots,
+/// rotated off the real axis so real polynomials don't start out symmetric.
+fn initial_guesses<F: Float>(c: &[Complex<F>], output: &mut [Complex<F>]) {
+    let d = c.len() - 1;
+    let lead = c[0].norm();
+    let mut radius = F::ZERO;
+    for (i, k) in c.iter().enumerate().skip(1) {
+        let r = nth_root(k.norm() / lead, i);
+        if r > radius {
+            radius = r;
+        }
+    }
+    if radius == F::ZERO || !radius.is_finite() {
+        radius = F::ONE;
+    }
+
+    let two = F::ONE + F::ONE;
+    let pi = F::ZERO.atan2(-F::ONE);
+    let offset = F::ONE / (two + two);
+    for (i, z) in output.iter_mut().enumerate().take(d) {
+        let theta = two * pi * F::from_usize(i) / F::from_usize(d) + offset;
+        let (s, c) = theta.sin_cos();
+        *z = Complex::new(radius * c, radius * s);
+    }
+}
+
+/// Checks whether the correction to z is negligible, or z is already a root
+/// to within the rounding error of evaluating the polynomial.
+#[inline]
+fn converged<F: Float>(c: &[Complex<F>], z: Complex<F>, p: Complex<F>, step: F, tol: F) -> bool {
+    let scale = if z.norm() > F::ONE { z.norm() } else { F::ONE };
+    step <= tol * scale || p.norm() <= F::EPSILON * eval_abs(c, z)
+}
+
+/// Aberth–Ehrlich iteration on the approximations z to the roots of the
+/// polynomial c, updating each in place as soon as its correction is known.
+/// Returns whether every approximation converged.
+fn aberth<F: Float>(c: &[Complex<F>], z: &mut [Complex<F>], tol: F) -> bool {
+    let one = Complex::new(F::ONE, F::ZERO);
+    for _ in 0..ABERTH_MAX_ITERATIONS {
+        let mut done = true;
+        for k in 0..z.len() {
+            let (p, dp) = eval_with_derivative(c, z[k]);
+            if p.norm() == F::ZERO {
+                continue;
+            }
+            let w = p / dp;
+            let mut s = zero();
+            for (j, &zj) in z.iter().enumerate() {
+                if j != k {
+                    s += one / (z[k] - zj);
+                }
+            }
+            let step = w / (one - w * s);
+            if !(step.re.is_finite() && step.im.is_finite()) {
+                done = false;
+                continue;
+            }
+            z[k] -= step;
+            done &= converged(c, z[k], p, step.norm(), tol);
+        }
+        if done {
+            return true;
+        }
+    }
+    false
+}
+
+/// Durand–Kerner (Weierstrass) iteration on the approximations z to the roots
+/// of the polynomial c. Returns whether every approximation converged.
+fn durand_kerner<F: Float>(c: &[Complex<F>], z: &mut [Complex<F>], tol: F) -> bool {
+    for _ in 0..DURAND_KERNER_MAX_ITERATIONS {
+        let mut done = true;
+        for k in 0..z.len() {
+            let (p, _) = eval_with_derivative(c, z[k]);
+            if p.norm() == F::ZERO {
+                continue;
+            }
+            let mut d = c[0];
+            for (j, &zj) in z.iter().enumerate() {
+                if j != k {
+                    d *= z[k] - zj;
+                }
+            }
+            let step = p / d;
+            if !(step.re.is_finite() && step.im.is_finite()) {
+                done = false;
+                continue;
+            }
+            z[k] -= step;
+            done &= converged(c, z[k], p, step.norm(), tol);
+        }
+        if done {
+            return true;
+        }
+    }
+    false
+}
+
+/// Prepares the complex coefficients of the polynomial, writing its roots at
+/// zero into the output. Returns the number of leading zero coefficients and
+/// the number of roots at zero.
+fn prepare<T, F: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    c: &mut [Complex<F>; N],
+    output: &mut [Complex<F>],
+) -> (usize, usize)
+where
+    T: Float + Into<Complex<F>>,
+{
+    for (e, &k) in c.iter_mut().zip(p.c.iter()) {
+        *e = k.into();
+    }
+    let s = c.iter().position(|k| k.norm() != F::ZERO).unwrap_or(N);
+    let zeros = c.iter().rev().take_while(|k| k.norm() == F::ZERO).count();
+    if s == N {
+        return (N, 0);
+    }
+    for z in output.iter_mut().take(zeros) {
+        *z = zero();
+    }
+    (s, zeros)
+}
+
+/// Sorts roots by real part, then imaginary part.
+fn sort<F: Float>(z: &mut [Complex<F>]) {
+    z.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+}
+
+/// Finds every root, real and complex, of a polynomial with real or complex
+/// coefficients by the Aberth–Ehrlich method, falling back to Durand–Kerner
+/// should it fail to converge.
+///
+/// All roots are refined simultaneously from points on a circle enclosing
+/// them, each correction being a Newton step deflated implicitly by the other
+/// approximations. Iteration stops once every correction is within a relative
+/// tol of its root, or every root is exact to within the rounding error of
+/// evaluating the polynomial. Roots are sorted by real and then imaginary part,
+/// and padded with NaN if leading coefficients are zero. Should neither method
+/// converge, every root but those at zero is NaN, so that no approximation
+/// passes for a root.
+pub fn roots<T, F: Float, const N: usize>(p: &Polynomial<T, N>, tol: F) -> [Complex<F>; N - 1]
+where
+    T: Float + Into<Complex<F>>,
+{
+    let mut output = [Complex::new(F::NAN, F::NAN); N - 1];
+    let mut c = [zero(); N];
+    let (s, zeros) = prepare(p, &mut c, &mut output);
+    if s + zeros + 1 >= N {
+        return output;
+    }
+    let c = &c[s..N - zeros];
+    let z = &mut output[zeros..N - 1 - s];
+
+    initial_guesses(c, z);
+    if !aberth(c, z, tol) {
+        initial_guesses(c, z);
+        if !durand_kerner(c, z, tol) {
+            z.fill(Complex::new(F::NAN, F::NAN));
+            return output;
+        }
+    }
+    sort(&mut output[..N - 1 - s]);
+    output
+}
+
+/// Finds every root, real and complex, of a polynomial with real or complex
+/// coefficients by the Durand–Kerner method alone. Converges more slowly than
+/// roots, but is useful as a cross-check. As with roots, every root but those
+/// at zero is NaN should it fail to converge.
+pub fn roots_durand_kerner<T, F: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    tol: F,
+) -> [Complex<F>; N - 1]
+where
+    T: Float + Into<Complex<F>>,
+{
+    let mut output = [Complex::new(F::NAN, F::NAN); N - 1];
+    let mut c = [zero(); N];
+    let (s, zeros) = prepare(p, &mut c, &mut output);
+    if s + zeros + 1 >= N {
+        return output;
+    }
+    let c = &c[s..N - zeros];
+    let z = &mut output[zeros..N - 1 - s];
+
+    initial_guesses(c, z);
+    if !durand_kerner(c, z, tol) {
+        z.fill(Complex::new(F::NAN, F::NAN));
+        return output;
+    }
+    sort(&mut output[..N - 1 - s]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::complex::c64;
+
+    // Matches roots up to ordering, as roots with equal real parts may sort
+    // either way
+    fn assert_roots(r: &[c64], expected: &[c64], tol: f64) {
+        assert_eq!(r.len(), expected.len());
+        let mut used = [false; 16];
+        for b in expected {
+            let i = r
+                .iter()
+                .enumerate()
+                .position(|(i, a)| !used[i] && (*a - *b).norm() < tol);
+            assert!(i.is_some(), "{} not found in {:?}", b, r);
+            used[i.unwrap()] = true;
+        }
+    }
+
+    #[test]
+    fn real_coefficients() {
+        let tol = 1e-12;
+
+        // x^2 - 3x + 5 has roots 1.5 ± i√11/2
+        let r = roots(&Polynomial::new([1., -3., 5.]), tol);
+        let h = 11_f64.sqrt() / 2.;
+        assert_roots(&r, &[c64::new(1.5, -h), c64::new(1.5, h)], tol);
+
+        // (x^2 + 1)(x - 4)(x + 0.5)
+        let p = Polynomial::new([1., 0., 1.]) * Polynomial::new([1., -3.5, -2.]);
+        let r = roots(&p, tol);
+        let expected = [
+            c64::new(-0.5, 0.),
+            c64::new(0., -1.),
+            c64::new(0., 1.),
+            c64::new(4., 0.),
+        ];
+        assert_roots(&r, &expected, tol);
+        let r = roots_durand_kerner(&p, tol);
+        assert_roots(&r, &expected, tol);
+
+        // x^8 - 1 has the eighth roots of unity
+        let r = roots(&Polynomial::new([1., 0., 0., 0., 0., 0., 0., 0., -1.]), tol);
+        for z in r.iter() {
+            assert!((z.norm() - 1.).abs() < tol);
+            assert!((z.powi(8) - c64::new(1., 0.)).norm() < tol);
+        }
+    }
+
+    #[test]
+    fn complex_coefficients() {
+        let tol = 1e-12;
+
+        // (x - i)(x - 2) = x^2 - (2 + i)x + 2i
+        let p = Polynomial::new([c64::new(1., 0.), c64::new(-2., -1.), c64::new(0., 2.)]);
+        let r = roots(&p, tol);
+        assert_roots(&r, &[c64::new(0., 1.), c64::new(2., 0.)], tol);
+
+        // (x - (1 + i))^2 (x + 3i)
+        let a = Polynomial::new([c64::new(1., 0.), c64::new(-1., -1.)]);
+        let b = Polynomial::new([c64::new(1., 0.), c64::new(0., 3.)]);
+        let r = roots(&(a * a * b), tol);
+        assert_roots(
+            &r,
+            &[c64::new(0., -3.), c64::new(1., 1.), c64::new(1., 1.)],
+            1e-6,
+        );
+    }
+
+    #[test]
+    fn degenerate() {
+        let tol = 1e-12;
+
+        // Roots at zero are split off exactly
+        let r = roots(&Polynomial::new([1., 5., -14., 0.]), tol);
+        assert_roots(
+            &r,
+            &[c64::new(-7., 0.), c64::new(0., 0.), c64::new(2., 0.)],
+            tol,
+        );
+
+        // Leading zeros leave NaN padding
+        let r = roots(&Polynomial::new([0., 1., 0., 4.]), tol);
+        assert_roots(&r[..2], &[c64::new(0., -2.), c64::new(0., 2.)], tol);
+        assert!(r[2].re.is_nan());
+
+        // Constant polynomials have no roots
+        let r = roots(&Polynomial::new([0., 3.]), tol);
+        assert!(r[0].re.is_nan());
+
+        // A NaN coefficient never converges, which leaves NaN rather than the
+        // initial guesses, apart from the exact root at zero
+        let p = Polynomial::new([1., f64::NAN, 2., 0.]);
+        for r in [roots(&p, tol), roots_durand_kerner(&p, tol)] {
+            assert_eq!(r[0], c64::new(0., 0.));
+            assert!(r[1].re.is_nan() && r[2].re.is_nan());
+        }
+
+        // f32 coefficients find roots in single precision
+        let r = roots(&Polynomial::new([1.0_f32, 0.0, 4.0]), 1e-6_f32);
+        assert!((r[0] - Complex::new(0.0, -2.0)).norm() < 1e-5);
+        assert!((r[1] - Complex::new(0.0, 2.0)).norm() < 1e-5);
+    }
+}
@@ -0,0 +1,140 @@
+//! Backward error and conditioning of computed roots.
+//!
+//! A computed root x is as good as the data allows when it is the exact root
+//! of a polynomial whose coefficients differ from the given ones by no more
+//! than their own uncertainty. The backward error measures the smallest such
+//! relative perturbation, and the condition number how far a perturbation of
+//! that size can move the root, so their product bounds the relative error of
+//! x to first order.
+
+use crate::{float::Float, polynomial::Polynomial, solvers::RootSet};
+
+/// The backward error, condition number and forward error bound of a
+/// computed root.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Analysis<T> {
+    pub backward_error: T,
+    pub condition: T,
+    pub forward_error: T,
+}
+
+/// Evaluates the polynomial with coefficient magnitudes at |x|, the scale of
+/// the perturbations in the backward error.
+#[inline]
+fn eval_abs<T: Float, const N: usize>(p: &Polynomial<T, N>, x: T) -> T {
+    let r = x.abs();
+    p.c.iter().fold(T::ZERO, |acc, k| acc * r + k.abs())
+}
+
+/// The smallest relative perturbation of the coefficients that makes x an
+/// exact root, |p(x)| / Σ|a_i||x|^i, following Oettli and Prager. Zero for an
+/// exact root, and of the order of the unit roundoff for a root that is as
+/// accurate as the coefficients. As zero coefficients admit no relative
+/// perturbation, a root at zero must be found exactly. The residual is
+/// evaluated with the compensated Horner scheme. Requires a real floating
+/// point type.
+pub fn backward_error<T: Float, const N: usize>(p: &Polynomial<T, N>, x: T) -> T {
+    let (y, _) = p.eval_compensated(x);
+    let scale = eval_abs(p, x);
+    if scale == T::ZERO {
+        // Only the zero polynomial vanishes at every coefficient scale
+        return T::ZERO;
+    }
+    y.abs() / scale
+}
+
+/// The relative condition number Σ|a_i||x|^i / (|x||p'(x)|) of a simple root
+/// x under relative perturbations of the coefficients, or the absolute one
+/// Σ|a_i||x|^i / |p'(x)| for a root at zero. Infinite for a multiple root,
+/// whose perturbations move it by their square root or worse.
+pub fn condition_number<T: Float, const N: usize>(p: &Polynomial<T, N>, x: T) -> T {
+    let [_, dp] = p.eval_with_derivatives::<1>(x);
+    let scale = eval_abs(p, x);
+    let d = if x == T::ZERO {
+        dp.abs()
+    } else {
+        x.abs() * dp.abs()
+    };
+    if d == T::ZERO {
+        T::INFINITY
+    } else {
+        scale / d
+    }
+}
+
+/// The backward error and condition number of a computed root, with their
+/// product as a first-order bound on its relative error.
+pub fn analyze<T: Float, const N: usize>(p: &Polynomial<T, N>, x: T) -> Analysis<T> {
+    let backward_error = backward_error(p, x);
+    let condition = condition_number(p, x);
+    let forward_error = if backward_error == T::ZERO {
+        T::ZERO
+    } else {
+        backward_error * condition
+    };
+    Analysis {
+        backward_error,
+        condition,
+        forward_error,
+    }
+}
+
+/// Whether every root of a set is the exact root of a polynomial within a
+/// relative coefficient perturbation of tol, so that none is numerically
+/// meaningless.
+pub fn is_backward_stable<T: Float, const N: usize, const CAP: usize>(
+    p: &Polynomial<T, N>,
+    roots: &RootSet<T, CAP>,
+    tol: T,
+) -> bool {
+    roots.iter().all(|&x| backward_error(p, x) <= tol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::blinn::Blinn;
+
+    #[test]
+    fn backward() {
+        // (x + 3)(x + 1)(x - 2)(x - 5)
+        let p = Polynomial::new([1., -3., -15., 19., 30.]);
+        assert_eq!(backward_error(&p, 2.), 0.);
+        let e = backward_error(&p, 2. + 1e-10);
+        assert!(e > 1e-12 && e < 1e-10);
+        assert!(backward_error(&p, 0.) > 0.5);
+
+        // Blinn's roots are backward stable
+        let r = Blinn::roots_quartic(&p);
+        assert!(is_backward_stable(&p, &r, 8. * f64::EPSILON));
+        let mut bad = RootSet::<f64, 1>::new();
+        bad.push(2.5);
+        assert!(!is_backward_stable(&p, &bad, 1e-3));
+    }
+
+    #[test]
+    fn conditioning() {
+        // Wilkinson's polynomial of degree 8, whose larger roots are far
+        // worse conditioned than the smaller ones
+        let mut p = Polynomial::new([1., -1.]) * Polynomial::new([1., -2.]);
+        let q = Polynomial::new([1., -3.]) * Polynomial::new([1., -4.]);
+        let s = Polynomial::new([1., -5.]) * Polynomial::new([1., -6.]);
+        let t = Polynomial::new([1., -7.]) * Polynomial::new([1., -8.]);
+        let w = p * q * s * t;
+        assert!(condition_number(&w, 6.) > 100. * condition_number(&w, 1.));
+
+        // A double root is infinitely ill-conditioned
+        p = Polynomial::new([1., -2., 1.]);
+        assert_eq!(condition_number(&p, 1.), f64::INFINITY);
+
+        // A root at zero of a polynomial with no constant term stays put
+        // under relative perturbations
+        let p = Polynomial::new([1., 0., -4., 0.]);
+        assert_eq!(condition_number(&p, 0.), 0.);
+
+        // The forward error bound matches the relative error to first order
+        let a = analyze(&w, 6. + 1e-9);
+        assert!((a.forward_error / (1e-9 / 6.) - 1.).abs() < 1e-3);
+        assert_eq!(a.forward_error, a.backward_error * a.condition);
+    }
+}
@@ -1,97 +1,55 @@
-use std::marker::PhantomData;
+use crate::{
+    float::Float,
+    polynomial::Polynomial,
+    solvers::{projective::ProjectiveRootSet, RootFinder, RootSet},
+};
 
-use crate::{float::Float, number::Number, polynomial::Polynomial};
+/// Closed-form solvers for real roots up to the quartic.
+#[derive(Copy, Clone, Debug)]
+pub struct Blinn;
 
-#[derive(Clone)]
-pub struct Blinn<T: Number<Type = T>, const N: usize> {
-    casper: PhantomData<T>,
-}
-
-impl<T: Number<Type = T>, const N: usize> Blinn<T, N>
-where
-    T: Float,
-{
+impl Blinn {
     #[inline]
     // #[target_feature(enable = "fma")]
-    pub fn roots_quadratic(p: &Polynomial<T, N>) -> [T; N + 0_usize.pow(N as u32 - 1) - 1] {
-        let mut output = [T::NAN; N + 0_usize.pow(N as u32 - 1) - 1]; // Ugly workaround for const generics
-
-        // Quadratic, has either one or two real roots or two complex roots
-        let [A, B, C] = [p.c[0], p.c[1] / (T::ONE + T::ONE), p.c[2]];
-        let D = B.mul_add(B, -(A * C));
-        if D.ge(&T::ZERO) {
-            // Roots are real, use Blinn's homogeneous algorithm
-            let E = D.sqrt();
-            let [x1, w1]: [T; 2];
-            let [x2, w2]: [T; 2];
-            if B > T::ZERO {
-                [x1, w1] = [-C, B + E];
-                [x2, w2] = [-B - E, A];
-            } else if B < T::ZERO {
-                let F = -B + E;
-                [x1, w1] = [F, A];
-                [x2, w2] = [C, F];
-            } else {
-                if A.abs().ge(&C.abs()) {
-                    let F = (-A * C).sqrt();
-                    [x1, w1] = [F, A];
-                    [x2, w2] = [-F, A];
-                } else {
-                    let F = (-A * C).sqrt();
-                    [x1, w1] = [-C, F];
-                    [x2, w2] = [C, F];
-                }
-            }
-            output[0] = x1 / w1;
-            output[1] = x2 / w2;
-            return output;
-            // return [x1 / w1, x2 / w2];
-        } else {
-            // Roots are complex
-            return output;
-            // return [T::NAN, T::NAN];
+    pub fn roots_quadratic<T: Float, const N: usize>(
+        p: &Polynomial<T, N>,
+    ) -> RootSet<T, { N - 1 }> {
+        let mut output = RootSet::new();
+        output.merge(&Self::roots_quadratic_nopoly(p.c[0], p.c[1], p.c[2]));
+        output
+    }
+
+    /// Real roots of ax^2 + bx + c by Blinn's homogeneous algorithm, which
+    /// puts a root at infinity rather than dividing by a vanishing a.
+    #[inline]
+    pub fn roots_quadratic_nopoly<T: Float>(a: T, b: T, c: T) -> RootSet<T, 2> {
+        let mut output = RootSet::new();
+        if let Some([[x1, w1], [x2, w2]]) = quadratic_pairs(a, b, c) {
+            output.push(x1 / w1);
+            output.push(x2 / w2);
         }
+        // Otherwise the roots are complex
+        output
     }
 
+    /// Real roots of the homogeneous ax^2 + bxw + cw^2 as (x, w) pairs, by
+    /// Blinn's algorithm, with a root at infinity for each vanishing leading
+    /// coefficient. Fails for the zero polynomial.
     #[inline]
-    pub fn roots_quadratic_nopoly(a: T, b: T, c: T) -> [T; 2] {
-        // Quadratic, has either one or two real roots or two complex roots
-        let [A, B, C] = [a, b / (T::ONE + T::ONE), c];
-        let D = B.mul_add(B, -(A * C));
-        if D.ge(&T::ZERO) {
-            // Roots are real, use Blinn's homogeneous algorithm
-            let E = D.sqrt();
-            let [x1, w1]: [T; 2];
-            let [x2, w2]: [T; 2];
-            if B > T::ZERO {
-                [x1, w1] = [-C, B + E];
-                [x2, w2] = [-B - E, A];
-            } else if B < T::ZERO {
-                let F = -B + E;
-                [x1, w1] = [F, A];
-                [x2, w2] = [C, F];
-            } else {
-                if A.abs().ge(&C.abs()) {
-                    let F = (-A * C).sqrt();
-                    [x1, w1] = [F, A];
-                    [x2, w2] = [-F, A];
-                } else {
-                    let F = (-A * C).sqrt();
-                    [x1, w1] = [-C, F];
-                    [x2, w2] = [C, F];
-                }
-            }
-            return [x1 / w1, x2 / w2];
-        } else {
-            // Roots are complex
-            return [T::NAN, T::NAN];
+    pub fn roots_quadratic_homogeneous<T: Float>(a: T, b: T, c: T) -> ProjectiveRootSet<T, 2> {
+        let mut output = ProjectiveRootSet::new();
+        if let Some([r1, r2]) = quadratic_pairs(a, b, c) {
+            output.push(r1);
+            output.push(r2);
         }
+        output
     }
 
     /// Slightly modified from Levien's version at https://github.com/linebender/kurbo/pull/224
     #[inline]
-    pub fn roots_cubic(p: &Polynomial<T, N>) -> [T; N + 0_usize.pow(N as u32 - 1) - 1] {
-        let mut output = [T::NAN; N + 0_usize.pow(N as u32 - 1) - 1];
+    #[allow(non_snake_case)]
+    pub fn roots_cubic<T: Float, const N: usize>(p: &Polynomial<T, N>) -> RootSet<T, { N - 1 }> {
+        let mut output = RootSet::new();
 
         let a_inv = p.c[0].recip();
         let ONE_THIRD: T = T::ONE / (T::ONE + T::ONE + T::ONE); // Should be const but can't use T here
@@ -100,10 +58,7 @@ where
         let d: T = p.c[3] * a_inv;
         if !(b.is_finite() && c.is_finite() && d.is_finite()) {
             // cubic coefficient is zero or nearly so.
-            let [r1, r2] = Blinn::<T, N>::roots_quadratic_nopoly(p.c[1], p.c[2], p.c[3]);
-            // return [r1, r2, T::NAN];
-            output[0] = r1;
-            output[1] = r2;
+            output.merge(&Self::roots_quadratic_nopoly(p.c[1], p.c[2], p.c[3]));
             return output;
         }
 
@@ -128,17 +83,16 @@ where
             let s: T = todo * (-h2).sqrt();
 
             // return [s.mul_add(r0, -b), s.mul_add(r1, -b), s.mul_add(r2, -b)];
-            output[0] = s.mul_add(r0, -b);
-            output[1] = s.mul_add(r1, -b);
-            output[2] = s.mul_add(r2, -b);
-            return output;
+            output.push(s.mul_add(r0, -b));
+            output.push(s.mul_add(r1, -b));
+            output.push(s.mul_add(r2, -b));
         } else if h == T::ZERO {
             let s = (-h2).sqrt().copysign(dp);
             // return [s - b, s.mul_add(-2., -b), T::NAN];
-            output[0] = s - b;
+            // s - b is the double root, equal to the other for a triple root
+            output.insert(s - b, 2, None);
             let todo: T = -(T::ONE + T::ONE);
-            output[1] = s.mul_add(todo, -b);
-            return output;
+            output.push(s.mul_add(todo, -b));
         } else {
             let todo: T = -(T::ONE / (T::ONE + T::ONE + T::ONE + T::ONE));
             let rt = (todo * h).sqrt();
@@ -146,48 +100,252 @@ where
             let r = todo * dp;
             let s = (r + rt).cbrt() + (r - rt).cbrt();
             // return [s - b, T::NAN, T::NAN];
-            output[0] = s - b;
+            output.push(s - b);
+        }
+        output
+    }
+
+    /// Ferrari's method, reducing the depressed quartic to a product of two
+    /// quadratics through the largest root of its resolvent cubic, so that
+    /// every root comes from roots_cubic and roots_quadratic without iteration.
+    #[inline]
+    pub fn roots_quartic<T: Float, const N: usize>(p: &Polynomial<T, N>) -> RootSet<T, { N - 1 }> {
+        let mut output = RootSet::new();
+
+        let a_inv = p.c[0].recip();
+        let a: T = p.c[1] * a_inv;
+        let b: T = p.c[2] * a_inv;
+        let c: T = p.c[3] * a_inv;
+        let d: T = p.c[4] * a_inv;
+        if !(a.is_finite() && b.is_finite() && c.is_finite() && d.is_finite()) {
+            // quartic coefficient is zero or nearly so.
+            let cubic = Polynomial::new([p.c[1], p.c[2], p.c[3], p.c[4]]);
+            output.merge(&Self::roots_cubic::<T, 4>(&cubic));
+            return output;
+        }
+
+        // Depress with x = y - a/4 to y^4 + p y^2 + q y + r
+        let two = T::ONE + T::ONE;
+        let four = two + two;
+        let eight = four + four;
+        let shift = a / four;
+        let a2 = a * a;
+        let p2: T = b - (two + T::ONE) * a2 / eight;
+        let q: T = c - a * b / two + a2 * a / eight;
+        let r: T = d - a * c / four + a2 * b / (four * four)
+            - (two + T::ONE) * a2 * a2 / (four * four * four * four);
+
+        // Biquadratic, y^2 = z with z^2 + p z + r = 0, where q vanishes to
+        // within the rounding of its terms
+        let q_scale = c.abs() + (a * b).abs() / two + (a2 * a).abs() / eight;
+        if q.abs() <= four * T::EPSILON * q_scale {
+            let z = Self::roots_quadratic_nopoly(T::ONE, p2, r);
+            for (z, k) in z.iter_with_multiplicity() {
+                if z >= T::ZERO {
+                    let y = z.sqrt();
+                    output.insert(y - shift, k, None);
+                    output.insert(-y - shift, k, None);
+                }
+            }
+            if z.failed() {
+                output.fail();
+            }
+            return output;
+        }
+
+        // The largest root m of the resolvent m^3 + p m^2 + (p^2/4 - r) m - q^2/8
+        // is positive whenever q is nonzero, and splits the quartic into
+        // y^2 ± s y + p/2 + m ∓ t with s^2 = 2m, t^2 = (p/2 + m)^2 - r and
+        // st = q/2. The larger of s and t is taken from m and the other from
+        // q, so that a tiny m, rounded or not, doesn't blow up t.
+        let resolvent = Polynomial::new([T::ONE, p2, p2 * p2 / four - r, -q * q / eight]);
+        let ms = Self::roots_cubic::<T, 4>(&resolvent);
+        let m = match ms.as_slice().last() {
+            Some(&m) if !ms.failed() => {
+                if m > T::ZERO {
+                    m
+                } else {
+                    T::ZERO
+                }
+            }
+            _ => {
+                output.fail();
+                return output;
+            }
+        };
+        let (s2, t2) = (two * m, (p2 / two + m) * (p2 / two + m) - r);
+        let (s, t) = if s2 >= t2 {
+            let s = s2.sqrt();
+            (s, q / (two * s))
+        } else {
+            let t = t2.sqrt().copysign(q);
+            (q / (two * t), t)
+        };
+        if !(s.is_finite() && t.is_finite()) {
+            output.fail();
             return output;
         }
+        let quadratics = [
+            [T::ONE, s, p2 / two + m - t],
+            [T::ONE, -s, p2 / two + m + t],
+        ];
+        for [k2, k1, k0] in quadratics {
+            for (y, k) in Self::roots_quadratic_nopoly(k2, k1, k0).iter_with_multiplicity() {
+                output.insert(y - shift, k, None);
+            }
+        }
+        output
+    }
+
+    /// Real roots of the homogeneous cubic as (x, w) pairs, as described by
+    /// roots_homogeneous.
+    #[inline]
+    pub fn roots_cubic_homogeneous<T: Float, const N: usize>(
+        p: &Polynomial<T, N>,
+    ) -> ProjectiveRootSet<T, { N - 1 }> {
+        homogeneous(p, Self::roots_cubic::<T, N>)
     }
 
-    // #[inline]
-    // pub fn roots_cubic_nopoly(a_: f64, b_: f64, c_: f64, d_: f64) -> [f64; 3] {
-    //     let a_inv = a_.recip();
-    //     const ONE_THIRD: f64 = 1. / 3.;
-    //     let b = b_ * (ONE_THIRD * a_inv);
-    //     let c = c_ * (ONE_THIRD * a_inv);
-    //     let d = d_ * a_inv;
-    //     if !(b.is_finite() && c.is_finite() && d.is_finite()) {
-    //         // cubic coefficient is zero or nearly so.
-    //         let [r1, r2] = Blinn::roots_quadratic_nopoly(b_, c_, d_);
-    //         return [r1, r2, f64::NAN];
-    //     }
-
-    //     let h0 = b * d - c * c;
-    //     let h1 = (-c).mul_add(b, d);
-    //     let h2 = (-b).mul_add(b, c);
-
-    //     let h = 4. * h0 * h2 - h1 * h1;
-    //     let dp = (-2.0 * b).mul_add(h2, h1);
-    //     if h > 0. {
-    //         let t = h.sqrt().atan2(-dp) * ONE_THIRD;
-    //         let (t_s, t_c) = t.sin_cos();
-    //         let r0 = t_c;
-    //         let ps = t_s * 3_f64.sqrt();
-    //         let r1 = 0.5 * (-t_c + ps);
-    //         let r2 = 0.5 * (-t_c - ps);
-    //         let s = 2.0 * (-h2).sqrt();
-
-    //         return [s.mul_add(r0, -b), s.mul_add(r1, -b), s.mul_add(r2, -b)];
-    //     } else if h == 0. {
-    //         let s = (-h2).sqrt().copysign(dp);
-    //         return [s - b, s.mul_add(-2., -b), f64::NAN];
-    //     } else {
-    //         let rt = (-0.25 * h).sqrt();
-    //         let r = -0.5 * dp;
-    //         let s = (r + rt).cbrt() + (r - rt).cbrt();
-    //         return [s - b, f64::NAN, f64::NAN];
-    //     }
-    // }
+    /// Real roots of the homogeneous quartic as (x, w) pairs, as described by
+    /// roots_homogeneous.
+    #[inline]
+    pub fn roots_quartic_homogeneous<T: Float, const N: usize>(
+        p: &Polynomial<T, N>,
+    ) -> ProjectiveRootSet<T, { N - 1 }> {
+        homogeneous(p, Self::roots_quartic::<T, N>)
+    }
+
+    /// Real roots of the polynomial taken as homogeneous in (x, w), up to the
+    /// quartic, where each vanishing leading coefficient is a root at
+    /// infinity, (1, 0), in place of the infinities and NaNs of dividing by
+    /// it. Roots at infinity and at zero are split off exactly, and the rest
+    /// are solved for in x / w or w / x, whichever keeps the larger of the
+    /// outer coefficients leading, so that a root near infinity is found as
+    /// accurately as one near zero, with a Newton step for each simple root
+    /// above the quadratic. Fails above the quartic, and for the zero
+    /// polynomial.
+    pub fn roots_homogeneous<T: Float, const N: usize>(
+        p: &Polynomial<T, N>,
+    ) -> ProjectiveRootSet<T, { N - 1 }> {
+        let mut output = ProjectiveRootSet::new();
+        match N {
+            1 => {
+                if p.c[0] == T::ZERO {
+                    output.fail();
+                }
+            }
+            2 => output.push([-p.c[1], p.c[0]]),
+            3 => {
+                // The zero polynomial leaves the pairs (0, 0), which fail
+                let r = Self::roots_quadratic_homogeneous(p.c[0], p.c[1], p.c[2]);
+                for (i, &root) in r.iter().enumerate() {
+                    output.insert(root, r.multiplicity(i));
+                }
+                if r.failed() {
+                    output.fail();
+                }
+            }
+            4 => output = Self::roots_cubic_homogeneous(p),
+            5 => output = Self::roots_quartic_homogeneous(p),
+            _ => output.fail(),
+        }
+        output
+    }
+}
+
+/// Dispatches on the degree, failing above the quartic.
+impl RootFinder for Blinn {
+    fn roots<T: Float, const N: usize>(p: &Polynomial<T, N>, _tol: T) -> RootSet<T, { N - 1 }> {
+        let mut output = RootSet::new();
+        match N {
+            1 => {
+                if p.c[0] == T::ZERO {
+                    output.fail();
+                }
+            }
+            2 => output.push(-p.c[1] / p.c[0]),
+            3 => output = Self::roots_quadratic(p),
+            4 => output = Self::roots_cubic(p),
+            5 => output = Self::roots_quartic(p),
+            _ => output.fail(),
+        }
+        output
+    }
+}
+
+/// The real roots of ax^2 + bxw + cw^2 as (x, w) pairs by Blinn's homogeneous
+/// algorithm, or None if they are complex.
+#[inline]
+#[allow(non_snake_case)]
+fn quadratic_pairs<T: Float>(a: T, b: T, c: T) -> Option<[[T; 2]; 2]> {
+    let [A, B, C] = [a, b / (T::ONE + T::ONE), c];
+    let D = B.mul_add(B, -(A * C));
+    if !D.ge(&T::ZERO) {
+        return None;
+    }
+    let E = D.sqrt();
+    if B > T::ZERO {
+        Some([[-C, B + E], [-B - E, A]])
+    } else if B < T::ZERO {
+        let F = -B + E;
+        Some([[F, A], [C, F]])
+    } else if A.abs().ge(&C.abs()) {
+        let F = (-A * C).sqrt();
+        Some([[F, A], [-F, A]])
+    } else {
+        let F = (-A * C).sqrt();
+        Some([[-C, F], [C, F]])
+    }
+}
+
+/// Finds the real roots of p as homogeneous in (x, w) with an affine solver,
+/// splitting off the roots at infinity and zero, and solving for x / w or for
+/// w / x, whichever has the larger leading coefficient.
+fn homogeneous<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    solve: fn(&Polynomial<T, N>) -> RootSet<T, { N - 1 }>,
+) -> ProjectiveRootSet<T, { N - 1 }> {
+    let mut output = ProjectiveRootSet::new();
+    let s = p.c.iter().take_while(|&&k| k == T::ZERO).count();
+    if s == N {
+        output.fail();
+        return output;
+    }
+    let z = p.c.iter().rev().take_while(|&&k| k == T::ZERO).count();
+    output.insert([T::ONE, T::ZERO], s);
+    output.insert([T::ZERO, T::ONE], z);
+    if s + z + 1 == N {
+        return output;
+    }
+
+    // The remaining factor, padded with leading zeros, which the solvers
+    // drop, in whichever chart has the larger leading coefficient
+    let reverse = p.c[s].abs() < p.c[N - 1 - z].abs();
+    let mut c = [T::ZERO; N];
+    for (e, &k) in c[s + z..].iter_mut().zip(p.c[s..N - z].iter()) {
+        *e = k;
+    }
+    if reverse {
+        c[s + z..].reverse();
+    }
+    let q = Polynomial::new(c);
+    let r = solve(&q);
+    for (t, k) in r.iter_with_multiplicity() {
+        // The closed forms find roots to within rounding of the largest, so
+        // a simple root much smaller, which is the one near infinity in the
+        // reversed chart, takes a Newton step, kept if it helps
+        let mut t = t;
+        if k == 1 {
+            let [y, dy] = q.eval_with_derivatives::<1>(t);
+            let next = t - y / dy;
+            if next.is_finite() && q.eval(next).abs() < y.abs() {
+                t = next;
+            }
+        }
+        output.insert(if reverse { [T::ONE, t] } else { [t, T::ONE] }, k);
+    }
+    if r.failed() {
+        output.fail();
+    }
+    output
 }
@@ -0,0 +1,347 @@
+//! Multiple roots and clusters of computed roots.
+//!
+//! A root of multiplicity m is perturbed by the rounding of the coefficients
+//! by their relative error raised to the power 1/m, so that solvers return a
+//! multiple root as m roots scattered about it, or as a single one that is
+//! off by as much, with multiplicity 1. Clustering groups computed roots that
+//! lie within tol of one another, and counts the roots of the polynomial in a
+//! disk enclosing each group by Pellet's test on its Taylor expansion about the
+//! centre. That count is the multiplicity of the cluster, and the mean of the
+//! roots it holds, found from the same expansion, is far more accurate than
+//! any of them.
+
+use core::ops::Index;
+
+use crate::{complex::Complex, float::Float, polynomial::Polynomial, solvers::RootSet};
+
+/// How many times the disk about a group may be doubled in size before
+/// Pellet's test is taken as inconclusive.
+const PELLET_DOUBLINGS: usize = 4;
+
+/// The coefficients of p(x + c) in ascending order, so that t[j] is
+/// p^(j)(c) / j!, by repeated synthetic division.
+fn taylor<T: Float, const N: usize>(p: &Polynomial<T, N>, c: T) -> [T; N] {
+    let mut t = p.c;
+    for k in 0..N {
+        for i in 1..N - k {
+            let prev = t[i - 1];
+            t[i] += prev * c;
+        }
+    }
+    t.reverse();
+    t
+}
+
+/// Counts the roots of the polynomial, with multiplicity, in the disk of
+/// radius r about c by Pellet's test. If one term |t_m| r^m of the Taylor
+/// expansion about c exceeds the sum of all the others, then by Rouché's
+/// theorem there are exactly m roots in the disk. Returns None if no term
+/// dominates, in which case the disk is too large or too small to tell.
+pub fn count_in_disk<T: Float, const N: usize>(p: &Polynomial<T, N>, c: T, r: T) -> Option<usize> {
+    let t = taylor(p, c);
+    let mut terms = [T::ZERO; N];
+    let mut power = T::ONE;
+    for (e, &k) in terms.iter_mut().zip(t.iter()) {
+        *e = k.abs() * power;
+        power *= r;
+    }
+    let (m, &largest) = terms
+        .iter()
+        .enumerate()
+        .fold((0, &T::ZERO), |a, b| if b.1 > a.1 { b } else { a });
+    let rest = terms.iter().fold(T::ZERO, |acc, &e| acc + e) - largest;
+    if largest > rest {
+        Some(m)
+    } else {
+        None
+    }
+}
+
+/// The root standing for the roots i..j of a set, with its multiplicity and
+/// error, as described by cluster.
+fn estimate<T: Float, const N: usize, const CAP: usize>(
+    p: &Polynomial<T, N>,
+    roots: &RootSet<T, CAP>,
+    i: usize,
+    j: usize,
+    tol: T,
+) -> (T, usize, T) {
+    let two = T::ONE + T::ONE;
+    let mut count = 0;
+    let mut sum = T::ZERO;
+    let mut error = T::ZERO;
+    for k in i..j {
+        let m = roots.multiplicity(k);
+        count += m;
+        sum += roots[k] * T::from_usize(m);
+        if let Some(e) = roots.error(k) {
+            if e > error {
+                error = e;
+            }
+        }
+    }
+    let c = sum / T::from_usize(count);
+
+    // Pellet's test needs the disk to be several times larger than the
+    // distance of its centre from the roots it holds
+    let mut r = (roots[j - 1] - roots[i]) / two + tol * scale(c);
+    for _ in 0..PELLET_DOUBLINGS {
+        if let Some(m) = count_in_disk(p, c, r) {
+            if m == 0 {
+                break;
+            }
+            // The m roots in the disk sum to m c - t_(m-1) / t_m
+            let t = taylor(p, c);
+            let x = c - t[m - 1] / (T::from_usize(m) * t[m]);
+            let x = if (x - c).abs() <= r { x } else { c };
+            return (x, m, if r > error { r } else { error });
+        }
+        r *= two;
+    }
+    let r = (roots[j - 1] - roots[i]) / two + tol * scale(c);
+    (c, count, if r > error { r } else { error })
+}
+
+#[inline]
+fn scale<T: Float>(x: T) -> T {
+    if x.abs() > T::ONE {
+        x.abs()
+    } else {
+        T::ONE
+    }
+}
+
+/// Groups the roots of a set that lie within tol of one another, relative to
+/// their magnitude where that exceeds one, and replaces each group with a
+/// single root. Its multiplicity is the number of roots of the polynomial in
+/// a disk enclosing the group, by Pellet's test, and its value the mean of
+/// those roots, as found from the Taylor expansion. The error of each root is
+/// the radius of the disk, which is doubled a few times should the test be
+/// inconclusive. Where it remains so, or finds no roots, the group keeps the
+/// mean and total multiplicity of its roots.
+///
+/// The roots of a set from any solver are accepted, so a multiple root that a
+/// solver returns as a single simple one is given its multiplicity. A root
+/// within tol of the disk of a group joins it, as the disk would otherwise
+/// count it twice.
+pub fn cluster<T: Float, const N: usize, const CAP: usize>(
+    p: &Polynomial<T, N>,
+    roots: &RootSet<T, CAP>,
+    tol: T,
+) -> RootSet<T, CAP> {
+    let mut output = RootSet::new();
+    if roots.failed() {
+        output.fail();
+    }
+    let mut i = 0;
+    while i < roots.len() {
+        // The group runs from root i to root j - 1
+        let mut j = i + 1;
+        loop {
+            while j < roots.len() && roots[j] - roots[j - 1] <= tol * scale(roots[j]) {
+                j += 1;
+            }
+            let (x, multiplicity, error) = estimate(p, roots, i, j, tol);
+            if j < roots.len() && roots[j] - x <= error + tol * scale(roots[j]) {
+                j += 1;
+                continue;
+            }
+            output.insert(x, multiplicity, Some(error));
+            break;
+        }
+        i = j;
+    }
+    output
+}
+
+/// A group of computed roots in the complex plane, taken as a single root.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Cluster<F: Float> {
+    /// The mean of the roots in the group.
+    pub center: Complex<F>,
+    /// The number of roots in the group.
+    pub multiplicity: usize,
+    /// The largest distance of a root in the group from its centre.
+    pub radius: F,
+}
+
+/// The clusters of a set of complex roots, holding at most CAP.
+#[derive(Copy, Clone, Debug)]
+pub struct Clusters<F: Float, const CAP: usize> {
+    clusters: [Cluster<F>; CAP],
+    len: usize,
+}
+
+impl<F: Float, const CAP: usize> Clusters<F, CAP> {
+    /// The number of clusters.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of roots counted with multiplicity.
+    pub fn count(&self) -> usize {
+        self.iter().map(|c| c.multiplicity).sum()
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[Cluster<F>] {
+        &self.clusters[..self.len]
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Cluster<F>> {
+        self.as_slice().iter()
+    }
+}
+
+impl<F: Float, const CAP: usize> Index<usize> for Clusters<F, CAP> {
+    type Output = Cluster<F>;
+
+    fn index(&self, i: usize) -> &Cluster<F> {
+        &self.as_slice()[i]
+    }
+}
+
+/// Groups complex roots, as from the Aberth–Ehrlich method or the companion
+/// matrix, that lie within tol of one another, relative to their magnitude
+/// where that exceeds one. Each cluster is centred on the mean of its roots,
+/// which for the roots scattered about a multiple root is far more accurate
+/// than any one of them. NaN padding is skipped, and clusters are in
+/// the order of their first roots.
+pub fn cluster_complex<F: Float, const M: usize>(z: &[Complex<F>; M], tol: F) -> Clusters<F, M> {
+    let zero = Complex::new(F::ZERO, F::ZERO);
+
+    // Single linkage, labelling each root with the first root of its group
+    let mut labels = [0; M];
+    for (i, e) in labels.iter_mut().enumerate() {
+        *e = i;
+    }
+    for i in 0..M {
+        for j in i + 1..M {
+            let d = (z[i] - z[j]).norm();
+            if labels[i] != labels[j] && d <= tol * scale(z[i].norm()) {
+                let (keep, drop) = (labels[i].min(labels[j]), labels[i].max(labels[j]));
+                for e in labels.iter_mut().filter(|e| **e == drop) {
+                    *e = keep;
+                }
+            }
+        }
+    }
+
+    let mut output = Clusters {
+        clusters: [Cluster {
+            center: zero,
+            multiplicity: 0,
+            radius: F::ZERO,
+        }; M],
+        len: 0,
+    };
+    for i in (0..M).filter(|&i| labels[i] == i && !z[i].re.is_nan()) {
+        let mut sum = zero;
+        let mut count = 0;
+        for k in (0..M).filter(|&k| labels[k] == i) {
+            sum += z[k];
+            count += 1;
+        }
+        let center = sum / Complex::new(F::from_usize(count), F::ZERO);
+        let radius = (0..M)
+            .filter(|&k| labels[k] == i)
+            .map(|k| (z[k] - center).norm())
+            .fold(F::ZERO, |a, b| if b > a { b } else { a });
+        output.clusters[output.len] = Cluster {
+            center,
+            multiplicity: count,
+            radius,
+        };
+        output.len += 1;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::{aberth, blinn::Blinn, companion};
+
+    #[test]
+    fn pellet() {
+        // (x - 1)(x - 2)^3
+        let p = Polynomial::new([1., -7., 18., -20., 8.]);
+        assert_eq!(count_in_disk(&p, 2.01, 0.1), Some(3));
+        assert_eq!(count_in_disk(&p, 1., 0.1), Some(1));
+        assert_eq!(count_in_disk(&p, 1.5, 0.1), Some(0));
+        assert_eq!(count_in_disk(&p, 1.5, 0.6), None);
+        assert_eq!(count_in_disk(&p, 0., 100.), Some(4));
+    }
+
+    #[test]
+    fn real() {
+        let tol = 1e-4;
+
+        // Blinn finds the double root of x^2 - 6x + 9 exactly
+        let p = Polynomial::new([1., -6., 9.]);
+        let r = cluster(&p, &Blinn::roots_quadratic(&p), tol);
+        assert_eq!(r, [3.]);
+        assert_eq!(r.multiplicity(0), 2);
+
+        // A solver that finds the triple root of (x - 1.1)^3 once, off by
+        // 1e-5
+        let p = Polynomial::new([1., -3.3, 3.63, -1.331]);
+        let mut found = RootSet::<f64, 3>::new();
+        found.push(1.10001);
+        let r = cluster(&p, &found, tol);
+        assert_eq!(r.len(), 1);
+        assert_eq!(r.multiplicity(0), 3);
+        assert!((r[0] - 1.1).abs() < 1e-12);
+
+        // A solver that scatters the quadruple root of (x - 2)^4 in two,
+        // further apart than tol but within tol of the disk about either
+        let p = Polynomial::new([1., -8., 24., -32., 16.]);
+        let mut found = RootSet::<f64, 4>::new();
+        found.insert(2. - 1.5e-4, 2, Some(1e-12));
+        found.insert(2. + 1.5e-4, 2, Some(1e-12));
+        let r = cluster(&p, &found, tol);
+        assert_eq!(r.len(), 1);
+        assert_eq!(r.multiplicity(0), 4);
+        assert!((r[0] - 2.).abs() < 1e-10);
+        assert!(r.error(0).unwrap() < 1e-2);
+
+        // Distinct roots further apart than tol are kept apart
+        let p = Polynomial::new([1., -3., 2.]);
+        let r = cluster(&p, &p.roots(1e-12), tol);
+        assert_eq!(r, [1., 2.]);
+        assert_eq!(r.count(), 2);
+    }
+
+    #[test]
+    fn complex() {
+        let tol = 1e-4;
+
+        // (x - 1.1)^3 (x^2 + 1) from the companion matrix and Aberth's method
+        let p = Polynomial::new([1., -3.3, 3.63, -1.331]) * Polynomial::new([1., 0., 1.]);
+        for z in [
+            companion::roots(&p),
+            aberth::roots::<f64, f64, 6>(&p, 1e-12),
+        ] {
+            let c = cluster_complex(&z, tol);
+            assert_eq!(c.len(), 3);
+            assert_eq!(c.count(), 5);
+            let triple = c.iter().find(|c| c.multiplicity == 3).unwrap();
+            assert!((triple.center - Complex::new(1.1, 0.)).norm() < 1e-6);
+            assert!(triple.radius > 1e-7 && triple.radius < tol);
+            assert!(c
+                .iter()
+                .all(|c| c.multiplicity == 3 || c.center.norm() > 0.99));
+        }
+
+        // NaN padding is skipped
+        let z = companion::roots(&Polynomial::new([0., 1., -1.]));
+        assert_eq!(cluster_complex(&z, tol).count(), 1);
+    }
+}
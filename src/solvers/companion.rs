@@ -0,0 +1,335 @@
+//! Roots as the eigenvalues of a companion matrix.
+//!
+//! The monic polynomial x^n + b_1 x^(n-1) + ... + b_n is the characteristic
+//! polynomial of its companion matrix, which has -b_1, ..., -b_n along its
+//! first row and ones along its subdiagonal. That matrix is already upper
+//! Hessenberg, so the shifted QR algorithm finds every root, real and complex,
+//! at once. Balanced first, this is backward stable, the roots being exact for
+//! a polynomial with coefficients perturbed by a small multiple of machine
+//! epsilon relative to their norm, which makes it a reference to cross-check
+//! the other solvers against. A Chebyshev series has the comrade matrix, also
+//! called the colleague matrix, which leaves the series in its own, better
+//! conditioned, basis.
+
+use crate::{
+    complex::Complex,
+    float::Float,
+    matrix::{self, Matrix},
+    orthogonal::{Chebyshev, OrthogonalPolynomial},
+    polynomial::Polynomial,
+    solvers::{RootFinder, RootSet},
+};
+
+/// Writes the companion matrix of the descending coefficients c, which must
+/// have a nonzero leading one, into the leading block of a.
+fn fill_companion<T: Float, const M: usize>(c: &[T], a: &mut [[T; M]]) {
+    let n = c.len() - 1;
+    for (e, &k) in a[0][..n].iter_mut().zip(c[1..].iter()) {
+        *e = -k / c[0];
+    }
+    for i in 1..n {
+        a[i][i - 1] = T::ONE;
+    }
+}
+
+/// Writes the comrade matrix of the ascending Chebyshev coefficients c, which
+/// must have a nonzero leading one, into the leading block of a. The matrix
+/// is transposed from its usual form, so as to be upper Hessenberg.
+fn fill_comrade<T: Float, const M: usize>(c: &[T], a: &mut [[T; M]]) {
+    let n = c.len() - 1;
+    let half = T::ONE / (T::ONE + T::ONE);
+
+    // x T_0 = T_1, and x T_k = (T_(k-1) + T_(k+1)) / 2 otherwise, where T_n
+    // is eliminated in terms of the lower T_k
+    for k in 0..n {
+        if k > 0 {
+            a[k - 1][k] = half;
+        }
+        if k + 1 < n {
+            a[k + 1][k] = if k == 0 { T::ONE } else { half };
+        }
+    }
+    let scale = if n == 1 { T::ONE } else { half };
+    for (row, &k) in a[..n].iter_mut().zip(c.iter()) {
+        row[n - 1] -= scale * k / c[n];
+    }
+}
+
+/// The companion matrix of the polynomial, whose eigenvalues are its roots.
+/// Requires a nonzero leading coefficient.
+pub fn companion<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+) -> Matrix<T, { N - 1 }, { N - 1 }> {
+    let mut m = Matrix::ZERO;
+    if N > 1 {
+        fill_companion(&p.c, &mut m.e);
+    }
+    m
+}
+
+/// The comrade matrix of the Chebyshev series, whose eigenvalues are its
+/// roots. Requires a nonzero leading coefficient.
+pub fn comrade<T: Float, const N: usize>(
+    p: &OrthogonalPolynomial<T, Chebyshev, N>,
+) -> Matrix<T, { N - 1 }, { N - 1 }> {
+    let mut m = Matrix::ZERO;
+    if N > 1 {
+        fill_comrade(&p.c, &mut m.e);
+    }
+    m
+}
+
+/// Sorts roots by real part, then imaginary part.
+fn sort<F: Float>(z: &mut [Complex<F>]) {
+    z.sort_by(|a, b| a.partial_cmp(b).unwrap_or(core::cmp::Ordering::Equal));
+}
+
+/// Finds every root, real and complex, of a real polynomial as the
+/// eigenvalues of its companion matrix. Roots at zero are split off exactly.
+/// Roots are sorted by real and then imaginary part, and padded with NaN if
+/// leading coefficients are zero or the QR algorithm fails to converge.
+pub fn roots<T: Float, const N: usize>(p: &Polynomial<T, N>) -> [Complex<T>; N - 1] {
+    let mut output = [Complex::new(T::NAN, T::NAN); N - 1];
+    let s = p.c.iter().position(|&k| k != T::ZERO).unwrap_or(N);
+    if s + 1 >= N {
+        return output;
+    }
+    let zeros = p.c.iter().rev().take_while(|&&k| k == T::ZERO).count();
+    for z in output.iter_mut().take(zeros) {
+        *z = Complex::new(T::ZERO, T::ZERO);
+    }
+
+    let c = &p.c[s..N - zeros];
+    let n = c.len() - 1;
+    if n > 0 {
+        let mut a = [[T::ZERO; N - 1]; N - 1];
+        fill_companion(c, &mut a);
+        if !matrix::eigenvalues(&mut a, n, &mut output[zeros..]) {
+            return [Complex::new(T::NAN, T::NAN); N - 1];
+        }
+    }
+    sort(&mut output[..N - 1 - s]);
+    output
+}
+
+/// Finds every root, real and complex, of a Chebyshev series as the
+/// eigenvalues of its comrade matrix, without converting it to monomials.
+/// Roots are sorted and padded as by roots.
+pub fn roots_chebyshev<T: Float, const N: usize>(
+    p: &OrthogonalPolynomial<T, Chebyshev, N>,
+) -> [Complex<T>; N - 1] {
+    let mut output = [Complex::new(T::NAN, T::NAN); N - 1];
+    let n = match p.c.iter().rposition(|&k| k != T::ZERO) {
+        Some(n) if n > 0 => n,
+        _ => return output,
+    };
+    let mut a = [[T::ZERO; N - 1]; N - 1];
+    fill_comrade(&p.c[..=n], &mut a);
+    if !matrix::eigenvalues(&mut a, n, &mut output) {
+        return [Complex::new(T::NAN, T::NAN); N - 1];
+    }
+    sort(&mut output[..n]);
+    output
+}
+
+/// Whether the pair of eigenvalues x ± iy, or x ± y if real, is a double real
+/// root at x split by rounding. The QR algorithm is backward stable, perturbing the
+/// coefficients c by a small multiple of ε relative to their magnitude, here
+/// taken as 2n ε Σ|c_i||x|^i, which moves a double root by as much as the
+/// square root of twice that over |p''(x)|, either along or off the real
+/// axis.
+fn is_split_double<T: Float>(c: &[T], x: T, y: T) -> bool {
+    let n = T::from_usize(c.len() - 1);
+    let two = T::ONE + T::ONE;
+
+    // Horner's rule for p and its first two derivatives, the last halved
+    let (mut p, mut dp, mut half_ddp) = (T::ZERO, T::ZERO, T::ZERO);
+    let mut magnitude = T::ZERO;
+    for &k in c {
+        half_ddp = half_ddp * x + dp;
+        dp = dp * x + p;
+        p = p * x + k;
+        magnitude = magnitude * x.abs() + k.abs();
+    }
+    half_ddp.abs() * y * y <= two * n * T::EPSILON * magnitude
+}
+
+/// Finds the real roots as the real eigenvalues of the companion matrix,
+/// taking an eigenvalue as real if its imaginary part is within tol of zero,
+/// relative to its magnitude where that exceeds one. A pair of eigenvalues
+/// that rounding has split off a double real root, which the QR algorithm
+/// can't return exactly, is taken as that root.
+pub struct Companion;
+
+impl RootFinder for Companion {
+    fn roots<T: Float, const N: usize>(p: &Polynomial<T, N>, tol: T) -> RootSet<T, { N - 1 }> {
+        let mut output = RootSet::new();
+        if N == 1 || p.c.iter().all(|&k| k == T::ZERO) {
+            if p.c[0] == T::ZERO {
+                output.fail();
+            }
+            return output;
+        }
+        let z = roots(p);
+        let d = N - 1 - p.c.iter().take_while(|&&k| k == T::ZERO).count();
+        if z[..d].iter().any(|z| z.re.is_nan()) {
+            output.fail();
+            return output;
+        }
+        let c = &p.c[N - 1 - d..];
+        let real = |z: &Complex<T>| {
+            let scale = if z.re.abs() > T::ONE {
+                z.re.abs()
+            } else {
+                T::ONE
+            };
+            z.im.abs() <= tol * scale
+        };
+        let two = T::ONE + T::ONE;
+        let mut i = 0;
+        while i < d {
+            let w = z[i];
+            i += 1;
+            if !real(&w) {
+                if w.im > T::ZERO && is_split_double(c, w.re, w.im) {
+                    output.insert(w.re, 2, None);
+                }
+                continue;
+            }
+
+            // A double root may equally be split along the real axis
+            if let Some(j) = z[i..d].iter().position(real) {
+                let (x, h) = ((w.re + z[i + j].re) / two, (z[i + j].re - w.re) / two);
+                if is_split_double(c, x, h) {
+                    output.insert(x, 2, None);
+                    i += j + 1;
+                    continue;
+                }
+            }
+            output.push(w.re);
+        }
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        complex::c64,
+        solvers::{blinn::Blinn, yuksel::Yuksel},
+    };
+
+    fn assert_roots(r: &[c64], expected: &[c64], tol: f64) {
+        assert_eq!(r.len(), expected.len());
+        for (a, b) in r.iter().zip(expected) {
+            assert!((*a - *b).norm() < tol, "{:?} != {:?}", r, expected);
+        }
+    }
+
+    #[test]
+    fn companion_roots() {
+        // (x^2 + 1)(x - 4)(x + 0.5)
+        let p = Polynomial::new([1., 0., 1.]) * Polynomial::new([1., -3.5, -2.]);
+        let m = companion(&p);
+        assert_eq!(m.e[0], [3.5, 1., 3.5, 2.]);
+        let expected = [
+            c64::new(-0.5, 0.),
+            c64::new(0., -1.),
+            c64::new(0., 1.),
+            c64::new(4., 0.),
+        ];
+        assert_roots(&roots(&p), &expected, 1e-13);
+        assert_roots(&m.eigenvalues().unwrap(), &expected, 1e-13);
+
+        // Roots at zero and leading zeros
+        let r = roots(&Polynomial::new([0., 1., 5., -14., 0.]));
+        assert_roots(
+            &r[..3],
+            &[c64::new(-7., 0.), c64::new(0., 0.), c64::new(2., 0.)],
+            1e-13,
+        );
+        assert!(r[3].re.is_nan());
+        assert!(roots(&Polynomial::new([0., 3.]))[0].re.is_nan());
+
+        // Wilkinson's polynomial of degree 10, which is ill conditioned
+        let mut p = Polynomial::new([0., 0., 0., 0., 0., 0., 0., 0., 0., 0., 1.]);
+        for k in 1..=10 {
+            let mut q = [0.; 11];
+            q[..10].copy_from_slice(&p.c[1..]);
+            for (e, &a) in q[1..].iter_mut().zip(p.c[1..].iter()) {
+                *e -= k as f64 * a;
+            }
+            p = Polynomial::new(q);
+        }
+        for (k, z) in roots(&p).iter().enumerate() {
+            assert!((z.re - (k + 1) as f64).abs() < 1e-6, "{}", z);
+            assert!(z.im.abs() < 1e-6, "{}", z);
+        }
+    }
+
+    #[test]
+    fn comrade_roots() {
+        // T_3 has roots ±√3/2 and 0
+        let p = OrthogonalPolynomial::<f64, Chebyshev, 4>::new([0., 0., 0., 1.]);
+        let h = 3_f64.sqrt() / 2.;
+        let expected = [c64::new(-h, 0.), c64::new(0., 0.), c64::new(h, 0.)];
+        assert_roots(&roots_chebyshev(&p), &expected, 1e-14);
+        assert_roots(&comrade(&p).eigenvalues().unwrap(), &expected, 1e-14);
+
+        // Matches the roots of the monomial form, with trailing zeros
+        let q = Polynomial::new([0., 2., -3., -1., 1.]);
+        let s = OrthogonalPolynomial::<f64, Chebyshev, 5>::from_monomial(&q);
+        let r = roots_chebyshev(&s);
+        assert_roots(&r[..3], &roots(&q)[..3], 1e-12);
+        assert!(r[3].re.is_nan());
+
+        let p = OrthogonalPolynomial::<f64, Chebyshev, 2>::new([0.5, 2.]);
+        assert_roots(&roots_chebyshev(&p), &[c64::new(-0.25, 0.)], 1e-15);
+    }
+
+    #[test]
+    fn cross_check() {
+        let tol = 1e-10;
+        let polynomials = [
+            Polynomial::new([0., 0., 1., -3., 2.]),
+            Polynomial::new([0., 1., -6., 11., -6.]),
+            Polynomial::new([0., 2., 0., -6., 2.]),
+            Polynomial::new([1., -3., -15., 19., 30.]),
+            Polynomial::new([1., 0., -5., 0., 4.]),
+            Polynomial::new([1., 0., 1., 0., -2.]),
+            Polynomial::new([1., 0., 0., 0., 1.]),
+        ];
+        for p in polynomials {
+            let a = p.roots_with::<Companion>(tol);
+            let b = p.roots_with::<Blinn>(tol);
+            let c = p.roots_with::<Yuksel>(tol);
+            assert_eq!(a.len(), b.len(), "{}", p);
+            assert_eq!(a.len(), c.len(), "{}", p);
+            for i in 0..a.len() {
+                assert!((a[i] - b[i]).abs() < 1e-12, "{}", p);
+                assert!((a[i] - c[i]).abs() < 1e-12, "{}", p);
+            }
+        }
+
+        // Multiple roots, which the QR algorithm splits into a conjugate pair
+        for p in [
+            Polynomial::new([1., 1., -3., -1., 2.]),
+            Polynomial::new([0., 1., -5., 7., -3.]),
+            Polynomial::new([1., -2., 2., -2., 1.]),
+        ] {
+            let a = p.roots_with::<Companion>(tol);
+            let c = p.roots_with::<Yuksel>(tol);
+            assert_eq!(a.len(), c.len(), "{}", p);
+            assert!(!a.failed(), "{}", p);
+            for i in 0..a.len() {
+                assert!((a[i] - c[i]).abs() < tol, "{}", p);
+                assert_eq!(a.multiplicity(i), c.multiplicity(i), "{}", p);
+            }
+        }
+        assert!(Polynomial::new([0.]).roots_with::<Companion>(tol).failed());
+        assert!(Polynomial::new([0., 3.])
+            .roots_with::<Companion>(tol)
+            .is_empty());
+    }
+}
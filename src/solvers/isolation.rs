@@ -0,0 +1,714 @@
+//! Real root isolation.
+//!
+//! Sturm chains count the distinct real roots of a polynomial in an interval,
+//! which can suggest that an interval holds no root at all. The method of
+//! Vincent, Akritas and Strzeboński splits the real line by continued
+//! fractions into disjoint intervals holding exactly one root each, guided by
+//! Descartes' rule of signs, and the roots are then refined within them by
+//! Yuksel's safeguarded Newton iterations.
+//!
+//! Both run in floating point, so the counts are exact only while the chain or
+//! transformed coefficients are computed without significant rounding, as for
+//! polynomials of moderate degree with integer-valued coefficients.
+
+use core::ops::Index;
+
+use crate::{
+    float::Float,
+    polynomial::{
+        derivative_in_place, div_exact, div_rem_in_place, gcd_in_place, leading, normalize,
+        Polynomial,
+    },
+    solvers::{
+        yuksel::{find_closed, horner, is_different_sign},
+        RootSet,
+    },
+};
+
+/// Depth of the continued fraction search, beyond which isolation fails.
+const VAS_STACK: usize = 64;
+
+/// Iteration limit for the continued fraction search.
+const VAS_MAX_ITERATIONS: usize = 4096;
+
+/// Bisection limit when narrowing an interval whose end is a root.
+const NARROW_MAX_ITERATIONS: usize = 256;
+
+/// An open interval (lo, hi) holding exactly one root, or the root itself
+/// where lo = hi.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+impl<T: Float> Interval<T> {
+    pub fn new(lo: T, hi: T) -> Self {
+        if hi < lo {
+            Self { lo: hi, hi: lo }
+        } else {
+            Self { lo, hi }
+        }
+    }
+
+    #[inline]
+    pub fn width(&self) -> T {
+        self.hi - self.lo
+    }
+
+    /// Whether the root was found exactly, closing the interval to a point.
+    #[inline]
+    pub fn is_exact(&self) -> bool {
+        self.lo == self.hi
+    }
+
+    #[inline]
+    pub fn contains(&self, x: T) -> bool {
+        if self.is_exact() {
+            x == self.lo
+        } else {
+            self.lo < x && x < self.hi
+        }
+    }
+}
+
+/// Disjoint isolating intervals in ascending order, one for each distinct real
+/// root.
+#[derive(Copy, Clone, Debug)]
+pub struct Isolation<T: Float, const CAP: usize> {
+    intervals: [Interval<T>; CAP],
+    len: usize,
+    failed: bool,
+}
+
+impl<T: Float, const CAP: usize> Isolation<T, CAP> {
+    fn new() -> Self {
+        Self {
+            intervals: [Interval::new(T::ZERO, T::ZERO); CAP],
+            len: 0,
+            failed: false,
+        }
+    }
+
+    fn push(&mut self, interval: Interval<T>) {
+        if self.len == CAP {
+            self.failed = true;
+            return;
+        }
+        let i = self.intervals[..self.len]
+            .iter()
+            .position(|k| interval.lo < k.lo)
+            .unwrap_or(self.len);
+        self.intervals.copy_within(i..self.len, i + 1);
+        self.intervals[i] = interval;
+        self.len += 1;
+    }
+
+    /// The number of intervals, and so of distinct real roots.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Whether the search gave up, in which case roots may be missing.
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
+    #[inline]
+    pub fn as_slice(&self) -> &[Interval<T>] {
+        &self.intervals[..self.len]
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, Interval<T>> {
+        self.as_slice().iter()
+    }
+}
+
+impl<T: Float, const CAP: usize> Index<usize> for Isolation<T, CAP> {
+    type Output = Interval<T>;
+
+    fn index(&self, i: usize) -> &Interval<T> {
+        &self.as_slice()[i]
+    }
+}
+
+/// Counts the sign changes in a sequence, ignoring zeros.
+fn variations<T: Float>(values: impl Iterator<Item = T>) -> usize {
+    let mut count = 0;
+    let mut last = T::ZERO;
+    for y in values {
+        if y != T::ZERO {
+            if last != T::ZERO && is_different_sign(last, y) {
+                count += 1;
+            }
+            last = y;
+        }
+    }
+    count
+}
+
+/// The Sturm chain of a polynomial: the polynomial, its derivative, and the
+/// negated remainders of their Euclidean algorithm.
+#[derive(Copy, Clone, Debug)]
+pub struct Sturm<T: Float, const N: usize> {
+    chain: [[T; N]; N],
+    len: usize,
+    tol: T,
+}
+
+impl<T: Float, const N: usize> Sturm<T, N> {
+    /// Builds the chain, taking a remainder whose coefficients are all within
+    /// tol of zero, relative to the largest, to be zero.
+    pub fn new(p: &Polynomial<T, N>, tol: T) -> Self {
+        let mut chain = [[T::ZERO; N]; N];
+        let mut len = 0;
+        chain[0] = p.c;
+        if normalize(&mut chain[0]) <= tol {
+            return Self { chain, len, tol };
+        }
+        len = 1;
+        if N > 1 {
+            derivative_in_place(&p.c, &mut chain[1]);
+            if normalize(&mut chain[1]) > tol {
+                len = 2;
+            }
+        }
+        // Positive scaling leaves the signs, and so the counts, unchanged
+        while len >= 2 && len < N {
+            let mut r = chain[len - 2];
+            let den = chain[len - 1];
+            let la = leading(&r, tol);
+            let lb = leading(&den, tol);
+            div_rem_in_place(&mut r[la..], &den[lb..]);
+            r[..lb + 1].fill(T::ZERO);
+            for e in r.iter_mut() {
+                *e = -*e;
+            }
+            if normalize(&mut r) <= tol {
+                break;
+            }
+            chain[len] = r;
+            len += 1;
+        }
+        Self { chain, len, tol }
+    }
+
+    /// The number of polynomials in the chain.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of sign changes along the chain evaluated at x.
+    pub fn sign_changes(&self, x: T) -> usize {
+        variations(self.chain[..self.len].iter().map(|c| horner(c, x)))
+    }
+
+    /// The number of sign changes along the chain at -∞ or ∞, given by the
+    /// leading coefficients.
+    fn sign_changes_at_infinity(&self, negative: bool) -> usize {
+        variations(self.chain[..self.len].iter().map(|c| {
+            let l = leading(c, self.tol);
+            if l == N {
+                T::ZERO
+            } else if negative && (N - 1 - l) % 2 == 1 {
+                -c[l]
+            } else {
+                c[l]
+            }
+        }))
+    }
+
+    /// The number of distinct real roots in (a, b], for a < b.
+    pub fn count(&self, a: T, b: T) -> usize {
+        self.sign_changes(a).saturating_sub(self.sign_changes(b))
+    }
+
+    /// The number of distinct real roots.
+    pub fn count_real(&self) -> usize {
+        self.sign_changes_at_infinity(true)
+            .saturating_sub(self.sign_changes_at_infinity(false))
+    }
+}
+
+/// Counts the distinct real roots of the polynomial in (a, b] by its Sturm
+/// chain. The chain is computed from rounded coefficients to within tol, so
+/// the count, and a count of zero in particular, is reliable only while its
+/// values at a and b are well separated from zero. It is no proof that an
+/// interval holds no root.
+pub fn count_roots<T: Float, const N: usize>(p: &Polynomial<T, N>, a: T, b: T, tol: T) -> usize {
+    Sturm::new(p, tol).count(a, b)
+}
+
+/// Replaces c with c(x + s), for the slice of coefficients of a polynomial.
+fn taylor_shift<T: Float>(c: &mut [T], s: T) {
+    let n = c.len() - 1;
+    for i in 0..n {
+        for k in 1..=(n - i) {
+            let prev = c[k - 1];
+            c[k] += s * prev;
+        }
+    }
+}
+
+/// An upper bound on the positive roots of the polynomial c with nonzero
+/// leading coefficient, as twice the largest (|a_k| / a_n)^(1/(n-k)) over the
+/// coefficients opposite in sign to the leading one, rounded up to a power of
+/// two. Zero if there are no positive roots.
+fn positive_bound<T: Float>(c: &[T]) -> T {
+    let two = T::ONE + T::ONE;
+    let lead = c[0].abs();
+    let mut bound = T::ZERO;
+    for (j, &k) in c.iter().enumerate().skip(1) {
+        if k == T::ZERO || !is_different_sign(k, c[0]) {
+            continue;
+        }
+        let ratio = k.abs() / lead;
+        if !ratio.is_finite() {
+            return T::INFINITY;
+        }
+        let mut t = T::ONE;
+        while t.powi(j as i32) < ratio {
+            t *= two;
+        }
+        while (t / two).powi(j as i32) >= ratio {
+            t /= two;
+        }
+        if t > bound {
+            bound = t;
+        }
+    }
+    two * bound
+}
+
+/// A lower bound on the positive roots of the polynomial c with nonzero
+/// leading and constant coefficients, from the upper bound on those of its
+/// reversal.
+fn positive_lower_bound<T: Float, const N: usize>(c: &[T]) -> T {
+    let mut r = [T::ZERO; N];
+    let r = &mut r[..c.len()];
+    for (e, &k) in r.iter_mut().zip(c.iter().rev()) {
+        *e = k;
+    }
+    let b = positive_bound(r);
+    if b == T::ZERO {
+        T::INFINITY
+    } else {
+        b.recip()
+    }
+}
+
+/// Scales the coefficients by a power of two to a largest magnitude in [1, 2),
+/// which unlike normalize leaves integer-valued coefficients exact.
+fn rescale<T: Float>(c: &mut [T]) {
+    let two = T::ONE + T::ONE;
+    let m = c
+        .iter()
+        .fold(T::ZERO, |m, k| if k.abs() > m { k.abs() } else { m });
+    if m == T::ZERO || !m.is_finite() {
+        return;
+    }
+    let mut t = T::ONE;
+    while m >= two * t {
+        t *= two;
+    }
+    while m < t {
+        t /= two;
+    }
+    for e in c.iter_mut() {
+        *e /= t;
+    }
+}
+
+/// A Möbius transformation x ↦ (ax + b) / (cx + d).
+type Mobius<T> = [T; 4];
+
+/// Divides the polynomial in c[s..] by x where its constant term vanishes,
+/// returning the new leading index.
+fn deflate_zero<T: Float>(c: &mut [T], s: usize) -> usize {
+    let n = c.len();
+    if s < n - 1 && c[n - 1] == T::ZERO {
+        c.copy_within(s..n - 1, s + 1);
+        c[s] = T::ZERO;
+        s + 1
+    } else {
+        s
+    }
+}
+
+/// Isolates the positive roots of the square-free polynomial c, with nonzero
+/// constant term, by the continued fraction method, writing their intervals
+/// into the output, mirrored to the negative axis if negate is set.
+fn vas<T: Float, const N: usize, const CAP: usize>(
+    c: &[T; N],
+    negate: bool,
+    output: &mut Isolation<T, CAP>,
+) {
+    let s = leading(c, T::ZERO);
+    if s + 1 >= N {
+        return;
+    }
+    let upper = positive_bound(&c[s..]);
+    let point = |x: T| {
+        if negate {
+            Interval::new(-x, -x)
+        } else {
+            Interval::new(x, x)
+        }
+    };
+    // The image of (0, ∞), bounded above by the roots
+    let bracket = |m: Mobius<T>| {
+        let [a, b, c, d] = m;
+        let lo = b / d;
+        let hi = if c == T::ZERO { upper } else { a / c };
+        if negate {
+            Interval::new(-hi, -lo)
+        } else {
+            Interval::new(lo, hi)
+        }
+    };
+
+    let mut stack = [([T::ZERO; 4], [T::ZERO; N]); VAS_STACK];
+    stack[0] = ([T::ONE, T::ZERO, T::ZERO, T::ONE], *c);
+    let mut top = 1;
+    let mut iterations = 0;
+    while top > 0 {
+        iterations += 1;
+        if iterations > VAS_MAX_ITERATIONS {
+            output.failed = true;
+            return;
+        }
+        top -= 1;
+        let (mut m, mut f) = stack[top];
+        let mut s = leading(&f, T::ZERO);
+        let mut v = variations(f[s..].iter().copied());
+        if v == 0 {
+            continue;
+        }
+        if v == 1 {
+            output.push(bracket(m));
+            continue;
+        }
+
+        // Skip ahead to the lower bound on the remaining roots
+        let lb = positive_lower_bound::<T, N>(&f[s..]).floor();
+        if lb >= T::ONE && lb.is_finite() {
+            taylor_shift(&mut f[s..], lb);
+            rescale(&mut f[s..]);
+            let [a, b, c, d] = m;
+            m = [a, a * lb + b, c, c * lb + d];
+            if f[N - 1] == T::ZERO {
+                output.push(point(m[1] / m[3]));
+                s = deflate_zero(&mut f, s);
+            }
+            v = variations(f[s..].iter().copied());
+            if v == 0 {
+                continue;
+            }
+            if v == 1 {
+                output.push(bracket(m));
+                continue;
+            }
+        }
+
+        // Roots in (1, ∞) by x ↦ x + 1
+        let [a, b, c, d] = m;
+        let mut f1 = f;
+        taylor_shift(&mut f1[s..], T::ONE);
+        rescale(&mut f1[s..]);
+        let m1 = [a, a + b, c, c + d];
+        let mut s1 = s;
+        let mut r = 0;
+        if f1[N - 1] == T::ZERO {
+            output.push(point(m1[1] / m1[3]));
+            s1 = deflate_zero(&mut f1, s1);
+            r = 1;
+        }
+        let v1 = variations(f1[s1..].iter().copied());
+
+        // Roots in (0, 1) by x ↦ 1 / (x + 1)
+        let m2 = [b, a + b, d, c + d];
+        let mut f2 = [T::ZERO; N];
+        let mut s2 = s;
+        let v2 = if v1 + r < v {
+            for (e, &k) in f2[s..].iter_mut().zip(f[s..].iter().rev()) {
+                *e = k;
+            }
+            taylor_shift(&mut f2[s..], T::ONE);
+            rescale(&mut f2[s..]);
+            if r == 1 {
+                s2 = deflate_zero(&mut f2, s2);
+            }
+            variations(f2[s2..].iter().copied())
+        } else {
+            0
+        };
+
+        for (v, m, f) in [(v1, m1, f1), (v2, m2, f2)] {
+            if v == 0 {
+                continue;
+            }
+            if v == 1 {
+                output.push(bracket(m));
+            } else if top == VAS_STACK {
+                output.failed = true;
+            } else {
+                stack[top] = (m, f);
+                top += 1;
+            }
+        }
+    }
+}
+
+/// The square-free part p / gcd(p, p') of the polynomial, trimmed of leading
+/// coefficients within tol of zero. Left undivided where the gcd is constant,
+/// so as not to round exact coefficients.
+fn square_free_part<T: Float, const N: usize>(p: &Polynomial<T, N>, tol: T) -> [T; N] {
+    let mut g = p.c;
+    let mut dp = [T::ZERO; N];
+    derivative_in_place(&p.c, &mut dp);
+    gcd_in_place(&mut g, &mut dp, tol);
+    let mut q = p.c;
+    if leading(&g, tol) < N - 1 {
+        div_exact(&p.c, &g, &mut q, tol);
+    }
+    rescale(&mut q);
+    let s = leading(&q, tol);
+    q[..s].fill(T::ZERO);
+    q
+}
+
+fn isolate_square_free<T: Float, const N: usize, const CAP: usize>(
+    q: &[T; N],
+    output: &mut Isolation<T, CAP>,
+) {
+    let mut q = *q;
+    let s = leading(&q, T::ZERO);
+    if s == N {
+        output.failed = true;
+        return;
+    }
+    if deflate_zero(&mut q, s) != s {
+        output.push(Interval::new(T::ZERO, T::ZERO));
+    }
+    vas(&q, false, output);
+
+    // Negative roots are the positive roots of q(-x)
+    for (i, e) in q.iter_mut().enumerate() {
+        if (N - 1 - i) % 2 == 1 {
+            *e = -*e;
+        }
+    }
+    vas(&q, true, output);
+}
+
+/// Narrows an isolating interval of a simple root until the polynomial f has
+/// nonzero values of opposite sign at its ends, as it may vanish at an end that
+/// is the root of a neighbouring interval. Returns the ends and the values at
+/// them, collapsed to a point should bisection land on the root, or None if
+/// rounding hides the sign change.
+fn narrow<T: Float>(
+    f: impl Fn(T) -> T,
+    df: impl Fn(T) -> T,
+    interval: Interval<T>,
+) -> Option<(T, T, T, T)> {
+    let two = T::ONE + T::ONE;
+    let Interval { mut lo, mut hi } = interval;
+    let (mut ylo, mut yhi) = (f(lo), f(hi));
+    // Just inside a simple root at the lower end, f takes the sign of f'
+    let slo = if ylo == T::ZERO { df(lo) } else { ylo };
+    for _ in 0..NARROW_MAX_ITERATIONS {
+        if ylo != T::ZERO && yhi != T::ZERO {
+            return is_different_sign(ylo, yhi).then_some((lo, hi, ylo, yhi));
+        }
+        let m = (lo + hi) / two;
+        if m <= lo || m >= hi {
+            return None;
+        }
+        let ym = f(m);
+        if ym == T::ZERO {
+            return Some((m, m, ym, ym));
+        }
+        if is_different_sign(slo, ym) {
+            hi = m;
+            yhi = ym;
+        } else {
+            lo = m;
+            ylo = ym;
+        }
+    }
+    None
+}
+
+/// Isolates the distinct real roots of a polynomial into disjoint intervals in
+/// ascending order, by continued fractions on its square-free part.
+///
+/// Repeated roots are found through gcd(p, p'), taking remainders within tol of
+/// zero to vanish. The zero polynomial, or a search that gives up, is marked
+/// as failed.
+pub fn isolate<T: Float, const N: usize>(p: &Polynomial<T, N>, tol: T) -> Isolation<T, { N - 1 }> {
+    let mut output = Isolation::new();
+    if leading(&p.c, T::ZERO) == N {
+        output.failed = true;
+        return output;
+    }
+    isolate_square_free(&square_free_part(p, tol), &mut output);
+    output
+}
+
+/// Finds the distinct real roots of a polynomial by isolating them, then
+/// refining each within its interval to tol, which is recorded as its error.
+pub fn roots<T: Float, const N: usize>(p: &Polynomial<T, N>, tol: T) -> RootSet<T, { N - 1 }> {
+    let mut output = RootSet::new();
+    if leading(&p.c, T::ZERO) == N {
+        output.fail();
+        return output;
+    }
+    let q = square_free_part(p, tol);
+    let mut intervals = Isolation::<T, { N - 1 }>::new();
+    isolate_square_free(&q, &mut intervals);
+    if intervals.failed() {
+        output.fail();
+    }
+
+    let s = leading(&q, T::ZERO);
+    let mut dq = [T::ZERO; N];
+    derivative_in_place(&q, &mut dq);
+    let f = |x| horner(&q[s..], x);
+    let df = |x| horner(&dq, x);
+    let two = T::ONE + T::ONE;
+    for interval in intervals.iter() {
+        let Interval { lo, hi } = *interval;
+        if interval.is_exact() {
+            output.insert(lo, 1, Some(T::ZERO));
+            continue;
+        }
+        match narrow(f, df, *interval) {
+            Some((x, y, _, _)) if x == y => output.insert(x, 1, Some(T::ZERO)),
+            Some((a, b, ya, _)) => {
+                let x = find_closed(N - 1 - s, f, df, a, b, ya, tol);
+                output.insert(x, 1, Some(tol));
+            }
+            None => {
+                // Rounding hid the sign change, so fall back on the interval
+                let x = (lo + hi) / two;
+                let e = interval.width() / two;
+                output.insert(x, 1, Some(if e > tol { e } else { tol }));
+            }
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_isolating<const CAP: usize>(r: &Isolation<f64, CAP>, expected: &[f64]) {
+        assert!(!r.failed());
+        assert_eq!(r.len(), expected.len());
+        for (interval, &x) in r.iter().zip(expected) {
+            assert!(interval.contains(x), "{} not in {:?}", x, interval);
+        }
+        for w in r.as_slice().windows(2) {
+            assert!(w[0].hi <= w[1].lo);
+        }
+    }
+
+    #[test]
+    fn sturm() {
+        // (x + 3)(x + 1)(x - 2)(x - 5)
+        let p = Polynomial::new([1., -3., -15., 19., 30.]);
+        let s = Sturm::new(&p, 1e-12);
+        assert_eq!(s.count_real(), 4);
+        assert_eq!(s.count(-10., 10.), 4);
+        assert_eq!(s.count(0., 3.), 1);
+        assert_eq!(s.count(2.5, 4.9), 0);
+        assert_eq!(count_roots(&p, -3.5, -0.5, 1e-12), 2);
+
+        // (x - 1)^2 (x + 2) has two distinct roots
+        let p = Polynomial::new([1., -1.]) * Polynomial::new([1., -1.]) * Polynomial::new([1., 2.]);
+        assert_eq!(Sturm::new(&p, 1e-12).count_real(), 2);
+
+        // x^4 + 1 has none
+        let p = Polynomial::new([1., 0., 0., 0., 1.]);
+        assert_eq!(Sturm::new(&p, 1e-12).count_real(), 0);
+    }
+
+    #[test]
+    fn isolation() {
+        let tol = 1e-12;
+
+        let p = Polynomial::new([1., -3., -15., 19., 30.]);
+        assert_isolating(&isolate(&p, tol), &[-3., -1., 2., 5.]);
+
+        // Roots at zero and close together
+        let p = Polynomial::new([1., 0.])
+            * Polynomial::new([1., -1.])
+            * Polynomial::new([1000., -1001.])
+            * Polynomial::new([1., 4.]);
+        assert_isolating(&isolate(&p, tol), &[-4., 0., 1., 1.001]);
+
+        // Repeated roots are isolated once
+        let p = Polynomial::new([1., -1.]) * Polynomial::new([1., -1.]) * Polynomial::new([1., 2.]);
+        assert_isolating(&isolate(&p, tol), &[-2., 1.]);
+
+        // (x^2 + 1)(x - 4)(x + 0.5) and x^4 + 1
+        let p = Polynomial::new([1., 0., 1.]) * Polynomial::new([1., -3.5, -2.]);
+        assert_isolating(&isolate(&p, tol), &[-0.5, 4.]);
+        let r = isolate(&Polynomial::new([1., 0., 0., 0., 1.]), tol);
+        assert!(r.is_empty() && !r.failed());
+
+        // Roots at 1, 2, ..., 8
+        let p = Polynomial::new([1., -1.])
+            * Polynomial::new([1., -2.])
+            * Polynomial::new([1., -3.])
+            * Polynomial::new([1., -4.])
+            * Polynomial::new([1., -5.])
+            * Polynomial::new([1., -6.])
+            * Polynomial::new([1., -7.])
+            * Polynomial::new([1., -8.]);
+        assert_isolating(&isolate(&p, tol), &[1., 2., 3., 4., 5., 6., 7., 8.]);
+
+        // The zero polynomial
+        assert!(isolate(&Polynomial::new([0., 0.]), tol).failed());
+    }
+
+    #[test]
+    fn refinement() {
+        let tol = 1e-12;
+
+        // (x + 2)(x + 1)(x - 0.5)(x - 1)(x - 3)(x - 4)
+        let p = Polynomial::new([1., 2.])
+            * Polynomial::new([1., 1.])
+            * Polynomial::new([1., -0.5])
+            * Polynomial::new([1., -1.])
+            * Polynomial::new([1., -3.])
+            * Polynomial::new([1., -4.]);
+        let r = roots(&p, tol);
+        assert_eq!(r.len(), 6);
+        for (a, b) in r.iter().zip([-2., -1., 0.5, 1., 3., 4.]) {
+            assert!((a - b).abs() < 1e-9);
+        }
+
+        // x^3 - 2x has roots 0 and ±√2
+        let r = roots(&Polynomial::new([1., 0., -2., 0.]), tol);
+        assert_eq!(r.len(), 3);
+        assert!((r[0] + 2_f64.sqrt()).abs() < 1e-9);
+        assert_eq!(r[1], 0.);
+        assert!((r[2] - 2_f64.sqrt()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,269 @@
+//! The storage shared by the sets of roots.
+//!
+//! A multiset keeps distinct keys in ascending order by a comparator, each
+//! with its multiplicity and a payload, such as an error bound, that is merged
+//! when an equal key is added again. Its columns are fixed arrays, so that a
+//! full set records the overflow rather than growing, or with the alloc
+//! feature vectors that grow as needed.
+
+use core::{cmp::Ordering, fmt};
+
+/// A column of a multiset, of which the first len entries are in use.
+pub(crate) trait Column<E: Copy> {
+    fn as_slice(&self, len: usize) -> &[E];
+
+    fn as_mut_slice(&mut self, len: usize) -> &mut [E];
+
+    /// Whether there is room for another entry after len.
+    fn has_room(&self, len: usize) -> bool;
+
+    /// Inserts e at i, shifting the entries from i to len along one.
+    fn insert(&mut self, len: usize, i: usize, e: E);
+}
+
+impl<E: Copy, const CAP: usize> Column<E> for [E; CAP] {
+    #[inline]
+    fn as_slice(&self, len: usize) -> &[E] {
+        &self[..len]
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self, len: usize) -> &mut [E] {
+        &mut self[..len]
+    }
+
+    #[inline]
+    fn has_room(&self, len: usize) -> bool {
+        len < CAP
+    }
+
+    fn insert(&mut self, len: usize, i: usize, e: E) {
+        self.copy_within(i..len, i + 1);
+        self[i] = e;
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: Copy> Column<E> for alloc::vec::Vec<E> {
+    #[inline]
+    fn as_slice(&self, len: usize) -> &[E] {
+        &self[..len]
+    }
+
+    #[inline]
+    fn as_mut_slice(&mut self, len: usize) -> &mut [E] {
+        &mut self[..len]
+    }
+
+    #[inline]
+    fn has_room(&self, _len: usize) -> bool {
+        true
+    }
+
+    #[inline]
+    fn insert(&mut self, _len: usize, i: usize, e: E) {
+        alloc::vec::Vec::insert(self, i, e);
+    }
+}
+
+/// How a multiset stores its columns.
+pub(crate) trait Storage {
+    type Column<E: Copy>: Column<E>;
+
+    /// An empty column, filled with e where it has a fixed size.
+    fn column<E: Copy>(e: E) -> Self::Column<E>;
+}
+
+/// Columns of CAP entries.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Fixed<const CAP: usize>;
+
+impl<const CAP: usize> Storage for Fixed<CAP> {
+    type Column<E: Copy> = [E; CAP];
+
+    #[inline]
+    fn column<E: Copy>(e: E) -> [E; CAP] {
+        [e; CAP]
+    }
+}
+
+/// Columns that grow as needed.
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Heap;
+
+#[cfg(feature = "alloc")]
+impl Storage for Heap {
+    type Column<E: Copy> = alloc::vec::Vec<E>;
+
+    #[inline]
+    fn column<E: Copy>(_e: E) -> alloc::vec::Vec<E> {
+        alloc::vec::Vec::new()
+    }
+}
+
+/// Distinct keys in ascending order, each with its multiplicity and a
+/// payload, along with whether anything was lost.
+pub(crate) struct Multiset<K: Copy, P: Copy, S: Storage> {
+    keys: S::Column<K>,
+    multiplicities: S::Column<usize>,
+    payloads: S::Column<P>,
+    len: usize,
+    failed: bool,
+}
+
+impl<K: Copy, P: Copy, S: Storage> Multiset<K, P, S> {
+    /// An empty multiset, with fixed columns filled with the given key and
+    /// payload.
+    pub fn new(key: K, payload: P) -> Self {
+        Self {
+            keys: S::column(key),
+            multiplicities: S::column(0),
+            payloads: S::column(payload),
+            len: 0,
+            failed: false,
+        }
+    }
+
+    /// Adds a key with its multiplicity and payload, placed by the comparator.
+    /// A key equal to one already in the set adds to its multiplicity and
+    /// merges its payload into that one's. A key that doesn't fit is recorded
+    /// by `failed`.
+    pub fn insert(
+        &mut self,
+        key: K,
+        multiplicity: usize,
+        payload: P,
+        cmp: impl Fn(&K, &K) -> Ordering,
+        merge: impl FnOnce(&mut P, P),
+    ) {
+        if multiplicity == 0 {
+            return;
+        }
+        let keys = self.keys.as_slice(self.len);
+        let i = keys
+            .iter()
+            .position(|k| cmp(&key, k) != Ordering::Greater)
+            .unwrap_or(self.len);
+        if i < self.len && cmp(&key, &keys[i]) == Ordering::Equal {
+            self.multiplicities.as_mut_slice(self.len)[i] += multiplicity;
+            merge(&mut self.payloads.as_mut_slice(self.len)[i], payload);
+            return;
+        }
+        if !self.keys.has_room(self.len) {
+            self.failed = true;
+            return;
+        }
+        self.keys.insert(self.len, i, key);
+        self.multiplicities.insert(self.len, i, multiplicity);
+        self.payloads.insert(self.len, i, payload);
+        self.len += 1;
+    }
+
+    #[inline]
+    pub fn fail(&mut self) {
+        self.failed = true;
+    }
+
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.failed
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// The number of keys counted with multiplicity.
+    pub fn count(&self) -> usize {
+        self.multiplicities().iter().sum()
+    }
+
+    #[inline]
+    pub fn keys(&self) -> &[K] {
+        self.keys.as_slice(self.len)
+    }
+
+    #[inline]
+    pub fn multiplicities(&self) -> &[usize] {
+        self.multiplicities.as_slice(self.len)
+    }
+
+    #[inline]
+    pub fn payloads(&self) -> &[P] {
+        self.payloads.as_slice(self.len)
+    }
+}
+
+impl<K: Copy, P: Copy, S: Storage> Clone for Multiset<K, P, S>
+where
+    S::Column<K>: Clone,
+    S::Column<usize>: Clone,
+    S::Column<P>: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            keys: self.keys.clone(),
+            multiplicities: self.multiplicities.clone(),
+            payloads: self.payloads.clone(),
+            len: self.len,
+            failed: self.failed,
+        }
+    }
+}
+
+impl<K: Copy, P: Copy, S: Storage> Copy for Multiset<K, P, S>
+where
+    S::Column<K>: Copy,
+    S::Column<usize>: Copy,
+    S::Column<P>: Copy,
+{
+}
+
+/// Compares the entries in use, and whether anything was lost.
+impl<K: Copy + PartialEq, P: Copy + PartialEq, S: Storage> PartialEq for Multiset<K, P, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.keys() == other.keys()
+            && self.multiplicities() == other.multiplicities()
+            && self.payloads() == other.payloads()
+            && self.failed == other.failed
+    }
+}
+
+impl<K: Copy + fmt::Debug, P: Copy + fmt::Debug, S: Storage> fmt::Debug for Multiset<K, P, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multiset")
+            .field("keys", &self.keys())
+            .field("multiplicities", &self.multiplicities())
+            .field("payloads", &self.payloads())
+            .field("failed", &self.failed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insertion() {
+        let mut s = Multiset::<i32, i32, Fixed<3>>::new(0, 0);
+        let cmp = |a: &i32, b: &i32| a.cmp(b);
+        for (k, p) in [(5, 1), (-2, 4), (5, 3), (0, 2)] {
+            s.insert(k, 1, p, cmp, |a, b| *a += b);
+        }
+        assert_eq!(s.keys(), [-2, 0, 5]);
+        assert_eq!(s.multiplicities(), [1, 1, 2]);
+        assert_eq!(s.payloads(), [4, 2, 4]);
+        assert_eq!(s.count(), 4);
+        assert!(!s.failed());
+
+        // A new key past the capacity is lost, but an equal one still merges
+        s.insert(7, 1, 0, cmp, |_, _| {});
+        assert!(s.failed());
+        s.insert(0, 2, 0, cmp, |_, _| {});
+        assert_eq!(s.multiplicities(), [1, 3, 2]);
+        assert_eq!(s.len(), 3);
+    }
+}
@@ -0,0 +1,334 @@
+//! Iterative polishing of approximate real roots.
+//!
+//! Newton's method converges quadratically to a simple root, Halley's method
+//! cubically at the cost of the second derivative, and Laguerre's method
+//! cubically with far better global behaviour, using the degree to take steps
+//! sized for the nearest root. Each iteration evaluates the polynomial with the
+//! compensated Horner scheme, so the correction stays accurate where the root
+//! is ill-conditioned, as for closed-form roots near a vanishing discriminant.
+
+use crate::{float::Float, polynomial::Polynomial, solvers::RootSet};
+
+/// Consecutive iterations of growing residual after which an iteration is
+/// taken to diverge.
+const DIVERGENCE_PATIENCE: usize = 3;
+
+/// The iteration used to polish a root.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Method {
+    Newton,
+    Halley,
+    Laguerre,
+}
+
+/// Why polishing stopped.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stop {
+    /// The last correction was within the relative tolerance.
+    Step,
+    /// The residual was within the rounding error of evaluating it, so no
+    /// further correction is meaningful.
+    Residual,
+    /// The iteration limit was reached first.
+    MaxIterations,
+    /// The iterates left the region holding the roots, stopped being finite,
+    /// or the residual kept growing. The best iterate is returned.
+    Diverged,
+}
+
+/// Stopping criteria for polishing.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Criteria<T> {
+    /// Stop once a correction is within tol relative to the root, or absolute
+    /// for roots smaller than one.
+    pub tol: T,
+    /// Stop once the residual is within the rounding error of evaluating it.
+    pub residual: bool,
+    pub max_iterations: usize,
+}
+
+impl<T: Float> Criteria<T> {
+    pub fn new(tol: T) -> Self {
+        Self {
+            tol,
+            residual: true,
+            max_iterations: 50,
+        }
+    }
+}
+
+/// A polished root, with an estimate of its error, the number of iterations
+/// taken and why they stopped. The error is the size of the last correction,
+/// or once the residual is within its rounding error, that rounding error over
+/// |p'(x)|, which is zero for a root found exactly.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Polished<T> {
+    pub root: T,
+    pub error: T,
+    pub iterations: usize,
+    pub stop: Stop,
+}
+
+impl<T: Float> Polished<T> {
+    #[inline]
+    pub fn converged(&self) -> bool {
+        matches!(self.stop, Stop::Step | Stop::Residual)
+    }
+}
+
+/// Cauchy's bound 1 + max |a_i / a_0| on the magnitude of every root.
+fn root_bound<T: Float>(c: &[T]) -> T {
+    let lead = c[0].abs();
+    let max = c[1..]
+        .iter()
+        .fold(T::ZERO, |m, k| if k.abs() > m { k.abs() } else { m });
+    T::ONE + max / lead
+}
+
+/// The correction to x by the given method, from p(x), p'(x) and p''(x) for a
+/// polynomial of degree n.
+fn step<T: Float>(method: Method, n: usize, y: [T; 3]) -> T {
+    let two = T::ONE + T::ONE;
+    let [p, dp, ddp] = y;
+    match method {
+        Method::Newton => p / dp,
+        Method::Halley => two * p * dp / (two * dp * dp - p * ddp),
+        Method::Laguerre => {
+            // a = n / (G ± √((n - 1)(nH - G²))), with G = p'/p and H = G² - p''/p,
+            // scaled through by p to avoid overflow near the root and with the
+            // radicand clamped at zero, as only real roots are sought
+            let k = T::from_usize(n);
+            let m = T::from_usize(n - 1);
+            let r = m * (m * dp * dp - k * p * ddp);
+            let s = if r > T::ZERO { r.sqrt() } else { T::ZERO };
+            let d = if dp < T::ZERO { dp - s } else { dp + s };
+            k * p / d
+        }
+    }
+}
+
+/// Polishes an approximate real root x0 of a polynomial by the given method.
+///
+/// Each iteration evaluates p with the compensated Horner scheme and its
+/// derivatives with Horner's, and stops by the criteria. Should the iterates
+/// diverge, the iterate of smallest residual is returned. Leading zero
+/// coefficients are ignored. Requires a real floating point type.
+pub fn polish<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    x0: T,
+    method: Method,
+    criteria: &Criteria<T>,
+) -> Polished<T> {
+    let s = p.c.iter().position(|&k| k != T::ZERO).unwrap_or(N);
+    let mut output = Polished {
+        root: x0,
+        error: T::INFINITY,
+        iterations: 0,
+        stop: Stop::Diverged,
+    };
+    if s + 1 >= N {
+        return output;
+    }
+    let n = N - 1 - s;
+    let bound = root_bound(&p.c[s..]);
+
+    let mut x = x0;
+    let mut best = (x0, T::INFINITY, T::INFINITY);
+    let mut last = T::INFINITY;
+    let mut growing = 0;
+    output.stop = Stop::MaxIterations;
+    for i in 0..criteria.max_iterations {
+        let (value, rounding) = p.eval_compensated(x);
+        let [_, dp, ddp] = p.eval_with_derivatives::<2>(x);
+        let residual = value.abs();
+        if residual < best.1 {
+            best = (x, residual, output.error);
+        }
+        output.iterations = i;
+        if criteria.residual && residual <= rounding {
+            output.error = if rounding == T::ZERO {
+                T::ZERO
+            } else {
+                rounding / dp.abs()
+            };
+            output.stop = Stop::Residual;
+            break;
+        }
+
+        // Diverging once the residual keeps growing
+        growing = if residual > last { growing + 1 } else { 0 };
+        last = residual;
+        let dx = step(method, n, [value, dp, ddp]);
+        if growing >= DIVERGENCE_PATIENCE || !dx.is_finite() {
+            output.stop = Stop::Diverged;
+            break;
+        }
+        x -= dx;
+        output.error = dx.abs();
+        output.iterations = i + 1;
+        if !x.is_finite() || x.abs() > bound {
+            output.stop = Stop::Diverged;
+            break;
+        }
+
+        let scale = if x.abs() > T::ONE { x.abs() } else { T::ONE };
+        if dx.abs() <= criteria.tol * scale {
+            output.stop = Stop::Step;
+            break;
+        }
+    }
+
+    if output.stop == Stop::Diverged {
+        output.root = best.0;
+        output.error = best.2;
+    } else {
+        output.root = x;
+    }
+    output
+}
+
+/// Polishes a root by Newton's method.
+#[inline]
+pub fn newton<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    x0: T,
+    criteria: &Criteria<T>,
+) -> Polished<T> {
+    polish(p, x0, Method::Newton, criteria)
+}
+
+/// Polishes a root by Halley's method.
+#[inline]
+pub fn halley<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    x0: T,
+    criteria: &Criteria<T>,
+) -> Polished<T> {
+    polish(p, x0, Method::Halley, criteria)
+}
+
+/// Polishes a root by Laguerre's method.
+#[inline]
+pub fn laguerre<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    x0: T,
+    criteria: &Criteria<T>,
+) -> Polished<T> {
+    polish(p, x0, Method::Laguerre, criteria)
+}
+
+/// Polishes every root of a set, as found by a closed-form solver, keeping
+/// their multiplicities and recording the error estimate of each, where it is
+/// finite, as its error. Roots whose polishing diverges are kept as they were.
+pub fn polish_roots<T: Float, const N: usize, const CAP: usize>(
+    p: &Polynomial<T, N>,
+    roots: &RootSet<T, CAP>,
+    method: Method,
+    criteria: &Criteria<T>,
+) -> RootSet<T, CAP> {
+    let mut output = RootSet::new();
+    for i in 0..roots.len() {
+        let r = polish(p, roots[i], method, criteria);
+        if r.stop == Stop::Diverged {
+            output.insert(roots[i], roots.multiplicity(i), roots.error(i));
+        } else {
+            let error = Some(r.error).filter(|e| e.is_finite());
+            output.insert(r.root, roots.multiplicity(i), error);
+        }
+    }
+    if roots.failed() {
+        output.fail();
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::blinn::Blinn;
+
+    #[test]
+    fn methods() {
+        // (x + 3)(x + 1)(x - 2)(x - 5)
+        let p = Polynomial::new([1., -3., -15., 19., 30.]);
+        let criteria = Criteria::new(1e-15);
+        for method in [Method::Newton, Method::Halley, Method::Laguerre] {
+            let r = polish(&p, 2.3, method, &criteria);
+            assert!(r.converged(), "{:?}", r);
+            assert!((r.root - 2.).abs() < 1e-14);
+            assert!(r.iterations < 10);
+        }
+
+        // Halley and Laguerre converge faster than Newton
+        let n = newton(&p, 5.6, &criteria);
+        let h = halley(&p, 5.6, &criteria);
+        let l = laguerre(&p, 5.6, &criteria);
+        assert!(n.converged() && h.converged() && l.converged());
+        assert!(h.iterations < n.iterations);
+        assert!(l.iterations < n.iterations);
+        assert!((l.root - 5.).abs() < 1e-14);
+
+        // Laguerre finds a root from far away
+        let r = laguerre(&p, 100., &criteria);
+        assert!(r.converged());
+        assert!((r.root - 5.).abs() < 1e-14);
+    }
+
+    #[test]
+    fn stopping() {
+        let p = Polynomial::new([1., -3., -15., 19., 30.]);
+
+        // An exact root stops on the residual at once
+        let r = newton(&p, -1., &Criteria::new(1e-15));
+        assert_eq!(r.stop, Stop::Residual);
+        assert_eq!(r.iterations, 0);
+        assert_eq!(r.error, 0.);
+
+        // The iteration limit is reported
+        let criteria = Criteria {
+            max_iterations: 2,
+            ..Criteria::new(0.)
+        };
+        let r = newton(&p, 7., &criteria);
+        assert_eq!(r.stop, Stop::MaxIterations);
+        assert_eq!(r.iterations, 2);
+
+        // x^2 + 1 has no real root, so Newton wanders off
+        let p = Polynomial::new([1., 0., 1.]);
+        let r = newton(&p, 0.5, &Criteria::new(1e-15));
+        assert_eq!(r.stop, Stop::Diverged);
+        assert!(!r.converged());
+    }
+
+    #[test]
+    fn polishing() {
+        // (x - 1)(x - 1 - 2^-20)(x - 3), exactly representable, whose close
+        // roots Blinn's closed-form cubic finds to only about 11 digits
+        let d = 2_f64.powi(-20);
+        let p = Polynomial::new([1., -1.])
+            * Polynomial::new([1., -(1. + d)])
+            * Polynomial::new([1., -3.]);
+        let r = Blinn::roots_cubic(&p);
+        assert!((r[0] - 1.).abs() > 1e-12);
+
+        let criteria = Criteria::new(1e-15);
+        for method in [Method::Newton, Method::Halley, Method::Laguerre] {
+            let polished = polish_roots(&p, &r, method, &criteria);
+            assert_eq!(polished.len(), 3);
+            for (a, b) in polished.iter().zip([1., 1. + d, 3.]) {
+                assert!((a - b).abs() < 1e-15);
+            }
+            assert!(polished.error(0).is_some());
+        }
+
+        // Already exact roots keep a finite, tiny error
+        let p = Polynomial::new([1., -3., 2.]);
+        let r = Blinn::roots_quadratic(&p);
+        assert_eq!(r, [1., 2.]);
+        let polished = polish_roots(&p, &r, Method::Newton, &Criteria::new(1e-15));
+        assert_eq!(polished, [1., 2.]);
+        for i in 0..2 {
+            assert!(polished.error(i).unwrap() <= f64::EPSILON);
+        }
+    }
+}
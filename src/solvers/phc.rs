@@ -0,0 +1,586 @@
+//! Polynomial homotopy continuation for square systems of multivariate
+//! polynomial equations, in the style of PHCpack.
+//!
+//! The target system f(x) = 0 is deformed from a start system g(x) = 0 with
+//! known solutions through the total-degree homotopy
+//!
+//! H(x, t) = (1 - t)·γ·g(x) + t·f(x),
+//!
+//! where g_i(x) = x_i^d_i - 1 and d_i is the degree of f_i. By Bézout's
+//! theorem the d_0·d_1·…·d_(V-1) start solutions include a path to every
+//! isolated solution of f, and for all but finitely many choices of the
+//! complex constant γ the paths stay nonsingular for t in [0, 1). Each path is
+//! tracked with a tangent predictor and a Newton corrector under adaptive step
+//! control; paths that diverge lead to solutions at infinity and are dropped.
+use crate::{complex::Complex, float::Float};
+
+/// A single term c·x_0^e_0·x_1^e_1·…·x_(V-1)^e_(V-1).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Monomial<T, const V: usize>
+where
+    T: Float,
+{
+    pub c: T,
+    pub e: [usize; V],
+}
+
+impl<T, const V: usize> Monomial<T, V>
+where
+    T: Float,
+{
+    pub const fn new(c: T, e: [usize; V]) -> Self {
+        Self { c, e }
+    }
+
+    /// Returns the total degree of the term.
+    pub fn degree(&self) -> usize {
+        self.e.iter().sum()
+    }
+}
+
+/// A multivariate polynomial in V variables, held as K terms. Terms with a
+/// zero coefficient are ignored, so equations of a system with different
+/// numbers of terms can be padded to the same K.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MultiPolynomial<T, const V: usize, const K: usize>
+where
+    T: Float,
+{
+    pub terms: [Monomial<T, V>; K],
+}
+
+impl<T, const V: usize, const K: usize> MultiPolynomial<T, V, K>
+where
+    T: Float,
+{
+    pub const fn new(terms: [Monomial<T, V>; K]) -> Self {
+        Self { terms }
+    }
+
+    /// Returns the total degree of the polynomial.
+    pub fn degree(&self) -> usize {
+        self.terms
+            .iter()
+            .filter(|m| m.c != T::ZERO)
+            .map(|m| m.degree())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Evaluates the polynomial at the point x.
+    pub fn eval<F: Float>(&self, x: &[Complex<F>; V]) -> Complex<F>
+    where
+        T: Into<Complex<F>>,
+    {
+        let mut output = Complex::new(F::ZERO, F::ZERO);
+        for m in self.terms.iter().filter(|m| m.c != T::ZERO) {
+            let mut y: Complex<F> = m.c.into();
+            for (&xi, &ei) in x.iter().zip(m.e.iter()) {
+                y *= xi.powi(ei as i32);
+            }
+            output += y;
+        }
+        output
+    }
+
+    /// Evaluates the gradient of the polynomial at the point x.
+    pub fn gradient<F: Float>(&self, x: &[Complex<F>; V]) -> [Complex<F>; V]
+    where
+        T: Into<Complex<F>>,
+    {
+        let mut output = [Complex::new(F::ZERO, F::ZERO); V];
+        for m in self.terms.iter().filter(|m| m.c != T::ZERO) {
+            for (j, g) in output.iter_mut().enumerate() {
+                if m.e[j] == 0 {
+                    continue;
+                }
+                let mut y: Complex<F> = m.c.into();
+                y *= F::from_usize(m.e[j]);
+                for (i, (&xi, &ei)) in x.iter().zip(m.e.iter()).enumerate() {
+                    let ei = if i == j { ei - 1 } else { ei };
+                    y *= xi.powi(ei as i32);
+                }
+                *g += y;
+            }
+        }
+        output
+    }
+}
+
+/// The isolated solutions found for a system, up to a capacity of S.
+///
+/// Endpoints x and y of two paths are taken as the same solution when
+/// |x - y| ≤ √tol·(1 + |x|) in the maximum norm, tol being that of the
+/// tracker. Newton's method only converges to about √tol on a double solution,
+/// so its paths are merged, while solutions further apart are kept distinct.
+#[derive(Copy, Clone, Debug)]
+pub struct Solutions<F, const V: usize, const S: usize>
+where
+    F: Float,
+{
+    pub points: [[Complex<F>; V]; S],
+    pub len: usize,
+    /// Number of paths that diverged or failed to converge.
+    pub failed: usize,
+    /// Whether more than S distinct solutions were found, the rest having been
+    /// dropped.
+    pub overflowed: bool,
+}
+
+impl<F, const V: usize, const S: usize> Solutions<F, V, S>
+where
+    F: Float,
+{
+    /// Iterates over the solutions found.
+    pub fn iter(&self) -> impl Iterator<Item = &[Complex<F>; V]> {
+        self.points[..self.len].iter()
+    }
+
+    /// Iterates over the solutions whose coordinates all have imaginary parts
+    /// within tol of zero, returning their real parts.
+    pub fn real(&self, tol: F) -> impl Iterator<Item = [F; V]> + '_ {
+        self.iter()
+            .filter(move |x| x.iter().all(|z| z.im.abs() <= tol))
+            .map(|x| core::array::from_fn(|i| x[i].re))
+    }
+
+    /// Adds the endpoint of a path unless it is the same solution as one
+    /// already found, for the tracker tolerance tol.
+    fn push(&mut self, x: [Complex<F>; V], tol: F) {
+        let threshold = tol.sqrt() * (F::ONE + norm(&x));
+        if self.iter().any(|y| distance(&x, y) <= threshold) {
+            return;
+        }
+        if self.len == S {
+            self.overflowed = true;
+            return;
+        }
+        self.points[self.len] = x;
+        self.len += 1;
+    }
+}
+
+/// Path tracking parameters for the total-degree homotopy.
+#[derive(Copy, Clone, Debug)]
+pub struct Tracker<F>
+where
+    F: Float,
+{
+    /// Constant of the gamma trick, which keeps paths apart for t < 1.
+    pub gamma: Complex<F>,
+    /// Relative tolerance for the corrector and the final Newton refinement.
+    /// Endpoints within its square root of one another are one solution.
+    pub tol: F,
+    /// Step size in t below which a path is abandoned.
+    pub min_step: F,
+    /// Norm beyond which a path is taken to be diverging to infinity.
+    pub max_norm: F,
+    /// Number of predictor-corrector steps after which a path is abandoned.
+    pub max_steps: usize,
+}
+
+impl<F> Tracker<F>
+where
+    F: Float,
+{
+    pub fn new(tol: F) -> Self {
+        // An arbitrary point on the unit circle, away from any rational angle
+        let theta = F::from_usize(1_234_567) / F::from_usize(1_000_000);
+        let (s, c) = theta.sin_cos();
+        let thousand = F::from_usize(1000);
+        Self {
+            gamma: Complex::new(c, s),
+            tol,
+            min_step: F::EPSILON * thousand,
+            max_norm: thousand * thousand * thousand * thousand,
+            max_steps: 10_000,
+        }
+    }
+
+    /// Solves the square system f(x) = 0 by tracking every path of the
+    /// total-degree homotopy, returning the distinct finite endpoints.
+    pub fn solve<T, const V: usize, const K: usize, const S: usize>(
+        &self,
+        f: &[MultiPolynomial<T, V, K>; V],
+    ) -> Solutions<F, V, S>
+    where
+        T: Float + Into<Complex<F>>,
+    {
+        let mut output = Solutions {
+            points: [[Complex::new(F::NAN, F::NAN); V]; S],
+            len: 0,
+            failed: 0,
+            overflowed: false,
+        };
+        let d: [usize; V] = core::array::from_fn(|i| f[i].degree());
+        if V == 0 || d.contains(&0) {
+            return output;
+        }
+        let paths: usize = d.iter().product();
+
+        let two = F::ONE + F::ONE;
+        let pi = F::ZERO.atan2(-F::ONE);
+        for m in 0..paths {
+            // Start solutions are the combinations of the d_i-th roots of unity
+            let mut x = [Complex::new(F::ZERO, F::ZERO); V];
+            let mut r = m;
+            for (xi, &di) in x.iter_mut().zip(d.iter()) {
+                let theta = two * pi * F::from_usize(r % di) / F::from_usize(di);
+                let (s, c) = theta.sin_cos();
+                *xi = Complex::new(c, s);
+                r /= di;
+            }
+            match self.track(f, &d, x) {
+                Some(x) => output.push(x, self.tol),
+                None => output.failed += 1,
+            }
+        }
+        output
+    }
+
+    /// Evaluates H(x, t), its Jacobian in x, and its derivative in t.
+    #[allow(clippy::type_complexity)]
+    fn homotopy<T, const V: usize, const K: usize>(
+        &self,
+        f: &[MultiPolynomial<T, V, K>; V],
+        d: &[usize; V],
+        x: &[Complex<F>; V],
+        t: F,
+    ) -> ([Complex<F>; V], [[Complex<F>; V]; V], [Complex<F>; V])
+    where
+        T: Float + Into<Complex<F>>,
+    {
+        let s = F::ONE - t;
+        let mut h = [Complex::new(F::ZERO, F::ZERO); V];
+        let mut jacobian = [[Complex::new(F::ZERO, F::ZERO); V]; V];
+        let mut ht = [Complex::new(F::ZERO, F::ZERO); V];
+        for i in 0..V {
+            let fi = f[i].eval(x);
+            let gi = (x[i].powi(d[i] as i32) - Complex::new(F::ONE, F::ZERO)) * self.gamma;
+            h[i] = gi * s + fi * t;
+            ht[i] = fi - gi;
+
+            let mut row = f[i].gradient(x);
+            for e in row.iter_mut() {
+                *e *= t;
+            }
+            let dg = x[i].powi(d[i] as i32 - 1) * self.gamma * F::from_usize(d[i]);
+            row[i] += dg * s;
+            jacobian[i] = row;
+        }
+        (h, jacobian, ht)
+    }
+
+    /// Newton corrector for H(·, t) from x, returning the corrected point if
+    /// it converges within a few contracting iterations.
+    fn correct<T, const V: usize, const K: usize>(
+        &self,
+        f: &[MultiPolynomial<T, V, K>; V],
+        d: &[usize; V],
+        mut x: [Complex<F>; V],
+        t: F,
+    ) -> Option<[Complex<F>; V]>
+    where
+        T: Float + Into<Complex<F>>,
+    {
+        let mut last = F::INFINITY;
+        for _ in 0..4 {
+            let (h, jacobian, _) = self.homotopy(f, d, &x, t);
+            let dx = solve_linear(jacobian, h)?;
+            let step = norm(&dx);
+            if step > last {
+                return None;
+            }
+            for (xi, dxi) in x.iter_mut().zip(dx.iter()) {
+                *xi -= *dxi;
+            }
+            if step <= self.tol * (F::ONE + norm(&x)) {
+                return Some(x);
+            }
+            last = step;
+        }
+        None
+    }
+
+    /// Tracks a single path from a start solution at t = 0 to t = 1.
+    fn track<T, const V: usize, const K: usize>(
+        &self,
+        f: &[MultiPolynomial<T, V, K>; V],
+        d: &[usize; V],
+        mut x: [Complex<F>; V],
+    ) -> Option<[Complex<F>; V]>
+    where
+        T: Float + Into<Complex<F>>,
+    {
+        let two = F::ONE + F::ONE;
+        let mut t = F::ZERO;
+        let mut h = F::ONE / F::from_usize(100);
+        let mut successes = 0;
+
+        for _ in 0..self.max_steps {
+            if t >= F::ONE {
+                break;
+            }
+            if h > F::ONE - t {
+                h = F::ONE - t;
+            }
+
+            // Tangent predictor, dx/dt = -H_x^-1 H_t
+            let (_, jacobian, ht) = self.homotopy(f, d, &x, t);
+            let dx = solve_linear(jacobian, ht)?;
+            let mut xp = x;
+            for (xi, dxi) in xp.iter_mut().zip(dx.iter()) {
+                *xi -= *dxi * h;
+            }
+
+            match self.correct(f, d, xp, t + h) {
+                Some(xc) => {
+                    x = xc;
+                    t += h;
+                    successes += 1;
+                    if successes >= 3 {
+                        h *= two;
+                        successes = 0;
+                    }
+                }
+                None => {
+                    h /= two;
+                    successes = 0;
+                    if h < self.min_step {
+                        return None;
+                    }
+                }
+            }
+            let n = norm(&x);
+            if !n.is_finite() || n > self.max_norm {
+                return None;
+            }
+        }
+        if t < F::ONE {
+            return None;
+        }
+
+        // Refine the endpoint on the target system itself
+        for _ in 0..32 {
+            let fx: [Complex<F>; V] = core::array::from_fn(|i| f[i].eval(&x));
+            let jacobian: [[Complex<F>; V]; V] = core::array::from_fn(|i| f[i].gradient(&x));
+            let dx = match solve_linear(jacobian, fx) {
+                Some(dx) => dx,
+                None => break,
+            };
+            for (xi, dxi) in x.iter_mut().zip(dx.iter()) {
+                *xi -= *dxi;
+            }
+            if norm(&dx) <= self.tol * (F::ONE + norm(&x)) {
+                break;
+            }
+        }
+        if x.iter().all(|z| z.re.is_finite() && z.im.is_finite()) {
+            Some(x)
+        } else {
+            None
+        }
+    }
+}
+
+/// Solves the square system f(x) = 0 with the default tracking parameters,
+/// returning up to S distinct isolated solutions. S should be at least the
+/// Bézout number, the product of the degrees of the equations.
+pub fn solve<T, F: Float, const V: usize, const K: usize, const S: usize>(
+    f: &[MultiPolynomial<T, V, K>; V],
+    tol: F,
+) -> Solutions<F, V, S>
+where
+    T: Float + Into<Complex<F>>,
+{
+    Tracker::new(tol).solve(f)
+}
+
+#[inline]
+fn norm<F: Float, const V: usize>(x: &[Complex<F>; V]) -> F {
+    x.iter()
+        .fold(F::ZERO, |m, z| if z.norm() > m { z.norm() } else { m })
+}
+
+#[inline]
+fn distance<F: Float, const V: usize>(x: &[Complex<F>; V], y: &[Complex<F>; V]) -> F {
+    let d: [Complex<F>; V] = core::array::from_fn(|i| x[i] - y[i]);
+    norm(&d)
+}
+
+/// Solves the complex linear system a·x = b by Gaussian elimination with
+/// partial pivoting, returning None if a is singular.
+fn solve_linear<F: Float, const V: usize>(
+    mut a: [[Complex<F>; V]; V],
+    mut b: [Complex<F>; V],
+) -> Option<[Complex<F>; V]> {
+    for k in 0..V {
+        let p = (k..V).fold(k, |p, i| {
+            if a[i][k].norm() > a[p][k].norm() {
+                i
+            } else {
+                p
+            }
+        });
+        if a[p][k].norm() == F::ZERO {
+            return None;
+        }
+        a.swap(k, p);
+        b.swap(k, p);
+        for i in (k + 1)..V {
+            let m = a[i][k] / a[k][k];
+            let row = a[k];
+            for (e, &r) in a[i].iter_mut().zip(row.iter()).skip(k) {
+                *e -= m * r;
+            }
+            let bk = b[k];
+            b[i] -= m * bk;
+        }
+    }
+    let mut x = [Complex::new(F::ZERO, F::ZERO); V];
+    for k in (0..V).rev() {
+        let mut s = b[k];
+        for j in (k + 1)..V {
+            s -= a[k][j] * x[j];
+        }
+        x[k] = s / a[k][k];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contains<const V: usize, const S: usize>(r: &Solutions<f64, V, S>, x: [f64; V]) -> bool {
+        r.real(1e-8)
+            .any(|y| y.iter().zip(x.iter()).all(|(a, b)| (a - b).abs() < 1e-8))
+    }
+
+    #[test]
+    fn line_and_circle() {
+        // x^2 + y^2 - 1 = 0, x - y = 0
+        let f = [
+            MultiPolynomial::new([
+                Monomial::new(1., [2, 0]),
+                Monomial::new(1., [0, 2]),
+                Monomial::new(-1., [0, 0]),
+            ]),
+            MultiPolynomial::new([
+                Monomial::new(1., [1, 0]),
+                Monomial::new(-1., [0, 1]),
+                Monomial::new(0., [0, 0]),
+            ]),
+        ];
+        let r: Solutions<f64, 2, 4> = solve(&f, 1e-12);
+        assert_eq!(r.len, 2);
+        assert!(!r.overflowed);
+        let h = 0.5_f64.sqrt();
+        assert!(contains(&r, [h, h]));
+        assert!(contains(&r, [-h, -h]));
+
+        // Too small a capacity drops a solution, which is recorded
+        let r: Solutions<f64, 2, 1> = solve(&f, 1e-12);
+        assert_eq!(r.len, 1);
+        assert!(r.overflowed);
+    }
+
+    #[test]
+    fn close_solutions() {
+        // (x - 1)(x - 1.0001) = 0, y - 1 = 0, whose solutions are 1e-4 apart,
+        // well beyond √tol
+        let f = [
+            MultiPolynomial::new([
+                Monomial::new(1., [2, 0]),
+                Monomial::new(-2.0001, [1, 0]),
+                Monomial::new(1.0001, [0, 0]),
+            ]),
+            MultiPolynomial::new([
+                Monomial::new(1., [0, 1]),
+                Monomial::new(-1., [0, 0]),
+                Monomial::new(0., [0, 0]),
+            ]),
+        ];
+        let r: Solutions<f64, 2, 2> = solve(&f, 1e-12);
+        assert_eq!(r.len, 2);
+        assert!(!r.overflowed);
+        assert!(contains(&r, [1., 1.]));
+        assert!(contains(&r, [1.0001, 1.]));
+    }
+
+    #[test]
+    fn two_circles() {
+        // x^2 + y^2 - 4 = 0, (x - 1)^2 + y^2 - 4 = 0, which also meet twice
+        // at the circular points at infinity
+        let f = [
+            MultiPolynomial::new([
+                Monomial::new(1., [2, 0]),
+                Monomial::new(1., [0, 2]),
+                Monomial::new(0., [1, 0]),
+                Monomial::new(-4., [0, 0]),
+            ]),
+            MultiPolynomial::new([
+                Monomial::new(1., [2, 0]),
+                Monomial::new(1., [0, 2]),
+                Monomial::new(-2., [1, 0]),
+                Monomial::new(-3., [0, 0]),
+            ]),
+        ];
+        let r: Solutions<f64, 2, 4> = solve(&f, 1e-12);
+        assert_eq!(r.len, 2);
+        assert_eq!(r.failed, 2);
+        let y = 3.75_f64.sqrt();
+        assert!(contains(&r, [0.5, y]));
+        assert!(contains(&r, [0.5, -y]));
+    }
+
+    #[test]
+    fn symmetric_system() {
+        // x + y + z = 6, xy + yz + zx = 11, xyz = 6, solved by every
+        // permutation of (1, 2, 3)
+        let f = [
+            MultiPolynomial::new([
+                Monomial::new(1., [1, 0, 0]),
+                Monomial::new(1., [0, 1, 0]),
+                Monomial::new(1., [0, 0, 1]),
+                Monomial::new(-6., [0, 0, 0]),
+            ]),
+            MultiPolynomial::new([
+                Monomial::new(1., [1, 1, 0]),
+                Monomial::new(1., [0, 1, 1]),
+                Monomial::new(1., [1, 0, 1]),
+                Monomial::new(-11., [0, 0, 0]),
+            ]),
+            MultiPolynomial::new([
+                Monomial::new(1., [1, 1, 1]),
+                Monomial::new(-6., [0, 0, 0]),
+                Monomial::new(0., [0, 0, 0]),
+                Monomial::new(0., [0, 0, 0]),
+            ]),
+        ];
+        let r: Solutions<f64, 3, 6> = solve(&f, 1e-12);
+        assert_eq!(r.len, 6);
+        for x in [
+            [1., 2., 3.],
+            [1., 3., 2.],
+            [2., 1., 3.],
+            [2., 3., 1.],
+            [3., 1., 2.],
+            [3., 2., 1.],
+        ] {
+            assert!(contains(&r, x));
+        }
+    }
+
+    #[test]
+    fn evaluation() {
+        let p = MultiPolynomial::new([Monomial::new(3., [2, 1]), Monomial::new(-1., [0, 1])]);
+        let x = [Complex::new(2., 0.), Complex::new(0., 1.)];
+        assert_eq!(p.degree(), 3);
+        assert_eq!(p.eval(&x), Complex::new(0., 11.));
+        assert_eq!(
+            p.gradient(&x),
+            [Complex::new(0., 12.), Complex::new(11., 0.)]
+        );
+    }
+}
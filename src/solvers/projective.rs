@@ -0,0 +1,194 @@
+//! Roots on the projective line.
+//!
+//! A polynomial of degree n in x is the homogeneous polynomial
+//! a_n x^n + a_(n-1) x^(n-1) w + ... + a_0 w^n in the pair (x, w), whose roots
+//! are the points (x, w) of the projective line, each standing for x / w. The
+//! point (1, 0) is the root at infinity, so that should the leading
+//! coefficient vanish, the degree it drops becomes roots there rather than the
+//! infinities or NaNs of dividing by it. Every polynomial of degree n but the
+//! zero polynomial has n roots on the projective line counted with
+//! multiplicity, of which the real ones are kept here.
+
+use core::{cmp::Ordering, ops::Index};
+
+use crate::{
+    float::Float,
+    solvers::{
+        multiset::{Fixed, Multiset},
+        rootset::ascending,
+        RootSet,
+    },
+};
+
+/// The real roots of a homogeneous polynomial as (x, w) pairs, holding at most
+/// CAP distinct roots along with the multiplicity of each. Pairs are scaled so
+/// that w is nonnegative, and x positive when w is zero, and kept in ascending
+/// order of x / w with the root at infinity last.
+#[derive(Copy, Clone, Debug)]
+pub struct ProjectiveRootSet<T: Float, const CAP: usize> {
+    set: Multiset<[T; 2], (), Fixed<CAP>>,
+}
+
+impl<T: Float, const CAP: usize> ProjectiveRootSet<T, CAP> {
+    pub fn new() -> Self {
+        Self {
+            set: Multiset::new([T::ZERO; 2], ()),
+        }
+    }
+
+    /// Adds a simple root.
+    #[inline]
+    pub fn push(&mut self, root: [T; 2]) {
+        self.insert(root, 1);
+    }
+
+    /// Adds a root with its multiplicity. A root equal to one already in the
+    /// set, as a point of the projective line, adds to its multiplicity. The
+    /// pair (0, 0) is no point at all, and is recorded by `failed`, as is a
+    /// NaN or a root past the capacity.
+    pub fn insert(&mut self, [x, w]: [T; 2], multiplicity: usize) {
+        if x.is_nan() || w.is_nan() || (x == T::ZERO && w == T::ZERO) {
+            self.set.fail();
+            return;
+        }
+        let root = if w < T::ZERO || (w == T::ZERO && x < T::ZERO) {
+            [-x, -w]
+        } else {
+            [x, w]
+        };
+        self.set.insert(root, multiplicity, (), order, |_, _| {});
+    }
+
+    /// Records that the solver broke down, so the set may be missing roots.
+    #[inline]
+    pub fn fail(&mut self) {
+        self.set.fail();
+    }
+
+    /// Whether the solver broke down, in which case the set may be missing
+    /// roots.
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.set.failed()
+    }
+
+    /// The number of distinct roots.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of roots counted with multiplicity.
+    pub fn count(&self) -> usize {
+        self.set.count()
+    }
+
+    /// The multiplicity of the ith root, or 0 past the end.
+    #[inline]
+    pub fn multiplicity(&self, i: usize) -> usize {
+        self.set.multiplicities().get(i).copied().unwrap_or(0)
+    }
+
+    /// The multiplicity of the root at infinity, which is the number of
+    /// vanishing leading coefficients.
+    pub fn at_infinity(&self) -> usize {
+        match self.as_slice().last() {
+            Some(&[_, w]) if w == T::ZERO => self.multiplicity(self.len() - 1),
+            _ => 0,
+        }
+    }
+
+    /// The distinct roots, in ascending order of x / w.
+    #[inline]
+    pub fn as_slice(&self) -> &[[T; 2]] {
+        self.set.keys()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, [T; 2]> {
+        self.as_slice().iter()
+    }
+
+    /// The finite roots as x / w, dropping the root at infinity.
+    pub fn affine(&self) -> RootSet<T, CAP> {
+        let mut output = RootSet::new();
+        for (i, &[x, w]) in self.iter().enumerate() {
+            if w != T::ZERO {
+                output.insert(x / w, self.multiplicity(i), None);
+            }
+        }
+        if self.failed() {
+            output.fail();
+        }
+        output
+    }
+}
+
+/// Orders points of the projective line, both scaled to a nonnegative w, by
+/// x / w, with the point at infinity last.
+#[inline]
+fn order<T: Float>(&[x1, w1]: &[T; 2], &[x2, w2]: &[T; 2]) -> Ordering {
+    if w1 == T::ZERO && w2 == T::ZERO {
+        Ordering::Equal
+    } else if w1 == T::ZERO {
+        Ordering::Greater
+    } else if w2 == T::ZERO {
+        Ordering::Less
+    } else {
+        ascending(&(x1 * w2), &(x2 * w1))
+    }
+}
+
+impl<T: Float, const CAP: usize> Default for ProjectiveRootSet<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, const CAP: usize> Index<usize> for ProjectiveRootSet<T, CAP> {
+    type Output = [T; 2];
+
+    fn index(&self, i: usize) -> &[T; 2] {
+        &self.as_slice()[i]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insertion() {
+        let mut r = ProjectiveRootSet::<f64, 4>::new();
+        r.push([1., 0.]);
+        r.push([3., -2.]);
+        r.push([2., 4.]);
+        r.push([-1., 0.]);
+        r.insert([1., 2.], 2);
+        assert_eq!(r.len(), 3);
+        assert_eq!(r.as_slice(), [[-3., 2.], [2., 4.], [1., 0.]]);
+        assert_eq!(r.multiplicity(1), 3);
+        assert_eq!(r.at_infinity(), 2);
+        assert_eq!(r.count(), 6);
+        assert!(!r.failed());
+
+        let a = r.affine();
+        assert_eq!(a, [-1.5, 0.5]);
+        assert_eq!(a.count(), 4);
+
+        r.push([0., 0.]);
+        assert!(r.failed() && r.affine().failed());
+
+        // A root past the capacity is recorded rather than panicking
+        let mut r = ProjectiveRootSet::<f64, 1>::new();
+        r.push([1., 1.]);
+        r.push([2., 1.]);
+        assert_eq!(r.len(), 1);
+        assert!(r.failed());
+    }
+}
@@ -0,0 +1,228 @@
+use core::{cmp::Ordering, ops::Index};
+
+use crate::{
+    float::Float,
+    solvers::multiset::{Fixed, Multiset},
+};
+
+/// The real roots found by a solver, holding at most CAP distinct roots in
+/// ascending order along with the multiplicity of each and, where the solver
+/// provides one, a bound on its error.
+///
+/// Roots at infinity, as when the leading coefficient vanishes, are not kept.
+/// A NaN root means the solver broke down, and is recorded by `failed` rather
+/// than kept, as is a root past the capacity, so an empty set always means
+/// there are no real roots.
+#[derive(Copy, Clone, Debug)]
+pub struct RootSet<T: Float, const CAP: usize> {
+    set: Multiset<T, Option<T>, Fixed<CAP>>,
+}
+
+impl<T: Float, const CAP: usize> RootSet<T, CAP> {
+    pub fn new() -> Self {
+        Self {
+            set: Multiset::new(T::ZERO, None),
+        }
+    }
+
+    /// Adds a simple root of unknown error.
+    #[inline]
+    pub fn push(&mut self, x: T) {
+        self.insert(x, 1, None);
+    }
+
+    /// Adds a root with its multiplicity and error bound. A root equal to one
+    /// already in the set adds to its multiplicity, keeping the larger error.
+    pub fn insert(&mut self, x: T, multiplicity: usize, error: Option<T>) {
+        if x.is_nan() {
+            self.set.fail();
+        } else if x.is_finite() {
+            self.set
+                .insert(x, multiplicity, error, ascending, keep_larger_error);
+        }
+    }
+
+    /// Adds every root of another set, along with its failure.
+    pub fn merge<const M: usize>(&mut self, other: &RootSet<T, M>) {
+        for i in 0..other.len() {
+            self.insert(other[i], other.multiplicity(i), other.error(i));
+        }
+        if other.failed() {
+            self.fail();
+        }
+    }
+
+    /// Records that the solver broke down, so the set may be missing roots.
+    #[inline]
+    pub fn fail(&mut self) {
+        self.set.fail();
+    }
+
+    /// Whether the solver broke down, in which case the set may be missing
+    /// roots.
+    #[inline]
+    pub fn failed(&self) -> bool {
+        self.set.failed()
+    }
+
+    /// The number of distinct roots.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.set.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The number of roots counted with multiplicity.
+    pub fn count(&self) -> usize {
+        self.set.count()
+    }
+
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        CAP
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize) -> Option<T> {
+        self.as_slice().get(i).copied()
+    }
+
+    /// The multiplicity of the ith root, or 0 past the end.
+    #[inline]
+    pub fn multiplicity(&self, i: usize) -> usize {
+        self.set.multiplicities().get(i).copied().unwrap_or(0)
+    }
+
+    /// The error bound on the ith root, if the solver provides one.
+    #[inline]
+    pub fn error(&self, i: usize) -> Option<T> {
+        self.set.payloads().get(i).copied().flatten()
+    }
+
+    /// The distinct roots in ascending order.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        self.set.keys()
+    }
+
+    #[inline]
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.as_slice().iter()
+    }
+
+    /// The distinct roots in ascending order, paired with their multiplicities.
+    pub fn iter_with_multiplicity(&self) -> impl Iterator<Item = (T, usize)> + '_ {
+        self.iter()
+            .copied()
+            .zip(self.set.multiplicities().iter().copied())
+    }
+}
+
+/// Orders roots, which are never NaN, in ascending order.
+#[inline]
+pub(crate) fn ascending<T: Float>(a: &T, b: &T) -> Ordering {
+    if a < b {
+        Ordering::Less
+    } else if a > b {
+        Ordering::Greater
+    } else {
+        Ordering::Equal
+    }
+}
+
+/// Merges the error bounds of two equal roots, keeping the larger.
+#[inline]
+pub(crate) fn keep_larger_error<T: Float>(a: &mut Option<T>, b: Option<T>) {
+    *a = match (*a, b) {
+        (Some(a), Some(b)) => Some(if a > b { a } else { b }),
+        (a, b) => a.or(b),
+    };
+}
+
+impl<T: Float, const CAP: usize> Default for RootSet<T, CAP> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Float, const CAP: usize> Index<usize> for RootSet<T, CAP> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        &self.as_slice()[i]
+    }
+}
+
+impl<'a, T: Float, const CAP: usize> IntoIterator for &'a RootSet<T, CAP> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: Float, const CAP: usize> PartialEq for RootSet<T, CAP> {
+    fn eq(&self, other: &Self) -> bool {
+        self.set == other.set
+    }
+}
+
+/// Compares the distinct roots alone, in ascending order.
+impl<T: Float, const CAP: usize, const M: usize> PartialEq<[T; M]> for RootSet<T, CAP> {
+    fn eq(&self, other: &[T; M]) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insertion() {
+        let mut r = RootSet::<f64, 4>::new();
+        assert!(r.is_empty());
+        r.push(2.);
+        r.push(-1.);
+        r.insert(0.5, 1, Some(1e-9));
+        r.push(2.);
+        assert_eq!(r, [-1., 0.5, 2.]);
+        assert_eq!(r.len(), 3);
+        assert_eq!(r.count(), 4);
+        assert_eq!(r.multiplicity(2), 2);
+        assert_eq!(r.error(1), Some(1e-9));
+        assert_eq!(r.error(0), None);
+        assert_eq!(r.get(3), None);
+        assert_eq!(r.capacity(), 4);
+        assert!(!r.failed());
+
+        let pairs: Vec<_> = r.iter_with_multiplicity().collect();
+        assert_eq!(pairs, [(-1., 1), (0.5, 1), (2., 2)]);
+    }
+
+    #[test]
+    fn non_finite() {
+        let mut r = RootSet::<f64, 2>::new();
+        r.push(f64::INFINITY);
+        r.push(f64::NEG_INFINITY);
+        assert!(r.is_empty() && !r.failed());
+        r.push(f64::NAN);
+        assert!(r.is_empty() && r.failed());
+    }
+
+    #[test]
+    fn overflow() {
+        let mut r = RootSet::<f64, 1>::new();
+        r.push(1.);
+        r.push(2.);
+        assert_eq!(r, [1.]);
+        assert!(r.failed());
+        r.push(1.);
+        assert_eq!(r.count(), 2);
+    }
+}
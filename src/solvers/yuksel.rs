@@ -27,30 +27,62 @@
 /// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
 /// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 /// SOFTWARE.
-use crate::polynomial::Polynomial;
+use crate::{
+    float::Float,
+    polynomial::Polynomial,
+    solvers::{RootFinder, RootSet},
+};
 
+/// Yuksel's method, for real roots of any degree.
+#[derive(Copy, Clone, Debug)]
+pub struct Yuksel;
+
+impl RootFinder for Yuksel {
+    fn roots<T: Float, const N: usize>(p: &Polynomial<T, N>, tol: T) -> RootSet<T, { N - 1 }> {
+        roots(p, tol)
+    }
+}
+
+#[inline]
+pub(crate) fn horner<T: Float>(c: &[T], x: T) -> T {
+    c.iter().fold(T::ZERO, |acc, &k| acc * x + k)
+}
+
+#[inline]
+pub(crate) fn is_different_sign<T: Float>(a: T, b: T) -> bool {
+    (a < T::ZERO) != (b < T::ZERO)
+}
+
+/// Finds the root of p in [x0, x1], where p(x0) = y0 and p(x1) have
+/// different signs, with safeguarded Newton iterations.
 #[inline]
-fn find_closed(
-    N: usize,
-    p: impl Fn(f64) -> f64,
-    dp: impl Fn(f64) -> f64,
-    mut x0: f64,
-    mut x1: f64,
-    y0: f64,
-    y1: f64,
-    tol: f64,
-) -> f64 {
-    let ep2 = 2. * tol;
-    let mut xr = (x0 + x1) / 2.;
+pub(crate) fn find_closed<T: Float>(
+    degree: usize,
+    p: impl Fn(T) -> T,
+    dp: impl Fn(T) -> T,
+    x0: T,
+    x1: T,
+    y0: T,
+    tol: T,
+) -> T {
+    let two = T::ONE + T::ONE;
+    let ep2 = two * tol;
+    let mut xr = (x0 + x1) / two;
     if x1 - x0 <= ep2 {
         return xr;
     }
 
-    if N <= 3 {
+    if degree <= 3 {
         let xr0 = xr;
         for _ in 0..16 {
             let mut xn = xr - p(xr) / dp(xr);
-            xn = xn.clamp(x0, x1);
+            xn = if xn < x0 {
+                x0
+            } else if xn > x1 {
+                x1
+            } else {
+                xn
+            };
             if (xr - xn).abs() <= tol {
                 return xn;
             }
@@ -66,7 +98,7 @@ fn find_closed(
     let mut xb1 = x1;
 
     loop {
-        let side = (y0 < 0.) != (yr < 0.);
+        let side = is_different_sign(y0, yr);
         if side {
             xb1 = xr;
         } else {
@@ -82,34 +114,33 @@ fn find_closed(
                 yr = p(xr);
             } else {
                 let mut xs;
-                if tol == 0. {
+                if tol == T::ZERO {
                     xs = if side {
-                        xb1 - f64::EPSILON
+                        xb1 - T::EPSILON
                     } else {
-                        xb0 + f64::EPSILON
+                        xb0 + T::EPSILON
                     };
                 } else {
-                    xs = xn - tol * if side { 1. } else { -1. };
+                    xs = if side { xn - tol } else { xn + tol };
                     if xs == xn {
                         xs = if side {
-                            xb1 - f64::EPSILON
+                            xb1 - T::EPSILON
                         } else {
-                            xb0 + f64::EPSILON
+                            xb0 + T::EPSILON
                         };
                     }
                 }
                 let ys = p(xs);
-                let s = (y0 < 0.) != (ys < 0.);
-                if side != s {
+                if side != is_different_sign(y0, ys) {
                     return xn;
                 };
                 xr = xs;
                 yr = ys;
             }
         } else {
-            xr = (xb0 + xb1) / 2.;
+            xr = (xb0 + xb1) / two;
             if xr == xb0 || xr == xb1 || xb1 - xb0 <= ep2 {
-                if tol == 0. {
+                if tol == T::ZERO {
                     let xm = if side { xb0 } else { xb1 };
                     let ym = p(xm);
                     if ym.abs() < yr.abs() {
@@ -124,62 +155,69 @@ fn find_closed(
     xr
 }
 
-// #[inline]
-// fn find_open(N: usize, p: impl Fn(f64) -> f64, dp: impl Fn(f64) -> f64, tol: f64) -> f64 {
-//     let mut xr = 0.;
-//     let yr = self.p.c[N];
-//     if (yr < 0.) != (self.p.c[0] < 0.) {
-//         find_open_max(N, p, dp, xr, yr, tol)
-//     } else {
-//         find_open_min(N, p, dp, xr, yr, tol)
-//     }
-// }
+/// Finds the single root of a monotonic polynomial with no critical points,
+/// searching outward from x = 0.
+#[inline]
+fn find_open<T: Float>(degree: usize, c: &[T], dp: impl Fn(T) -> T, tol: T) -> T {
+    let p = |x| horner(c, x);
+    let xr = T::ZERO;
+    let yr = c[c.len() - 1];
+    if is_different_sign(yr, c[0]) {
+        find_open_max(degree, p, dp, xr, yr, tol)
+    } else {
+        find_open_min(degree, p, dp, xr, yr, tol)
+    }
+}
 
+/// Finds the root of p in (x0, ∞), where p(x0) = y0.
 #[inline]
-fn find_open_max(
-    N: usize,
-    p: impl Fn(f64) -> f64,
-    dp: impl Fn(f64) -> f64,
-    mut x0: f64,
-    mut y0: f64,
-    tol: f64,
-) -> f64 {
-    find_open_helper(N, p, dp, x0, y0, x0 + 1., tol, false)
+fn find_open_max<T: Float>(
+    degree: usize,
+    p: impl Fn(T) -> T,
+    dp: impl Fn(T) -> T,
+    x0: T,
+    y0: T,
+    tol: T,
+) -> T {
+    find_open_helper(degree, p, dp, x0, y0, x0 + T::ONE, tol, false)
 }
+
+/// Finds the root of p in (-∞, x1), where p(x1) = y1.
 #[inline]
-fn find_open_min(
-    N: usize,
-    p: impl Fn(f64) -> f64,
-    dp: impl Fn(f64) -> f64,
-    mut x1: f64,
-    mut y1: f64,
-    tol: f64,
-) -> f64 {
-    find_open_helper(N, p, dp, x1, y1, x1 - 1., tol, true)
+fn find_open_min<T: Float>(
+    degree: usize,
+    p: impl Fn(T) -> T,
+    dp: impl Fn(T) -> T,
+    x1: T,
+    y1: T,
+    tol: T,
+) -> T {
+    find_open_helper(degree, p, dp, x1, y1, x1 - T::ONE, tol, true)
 }
 
 #[inline]
-fn find_open_helper(
-    N: usize,
-    p: impl Fn(f64) -> f64,
-    dp: impl Fn(f64) -> f64,
-    mut xm: f64,
-    mut ym: f64,
-    mut xr: f64,
-    tol: f64,
-    openMin: bool,
-) -> f64 {
-    let mut delta = 1.;
+#[allow(clippy::too_many_arguments)]
+fn find_open_helper<T: Float>(
+    degree: usize,
+    p: impl Fn(T) -> T,
+    dp: impl Fn(T) -> T,
+    mut xm: T,
+    mut ym: T,
+    mut xr: T,
+    tol: T,
+    open_min: bool,
+) -> T {
+    let mut delta = T::ONE;
     let mut yr = p(xr);
 
-    let mut otherside: bool = (ym < 0.) != (yr < 0.);
+    let mut otherside = is_different_sign(ym, yr);
 
-    'main_loop: while yr != 0. {
+    'main_loop: while yr != T::ZERO {
         if otherside {
-            if openMin {
-                return find_closed(N, p, dp, xr, xm, yr, ym, tol);
+            if open_min {
+                return find_closed(degree, p, dp, xr, xm, yr, tol);
             } else {
-                return find_closed(N, p, dp, xm, xr, ym, yr, tol);
+                return find_closed(degree, p, dp, xm, xr, ym, tol);
             }
         } else {
             'open_interval: loop {
@@ -188,17 +226,17 @@ fn find_open_helper(
                 let dy = dp(xr);
                 let dx = yr / dy;
                 let xn = xr - dx;
-                let dif = if openMin { xr - xn } else { xn - xr }; // Consider using |xr-xn|...
-                if dif <= 0. && xn.is_finite() {
+                // Valid Newton steps move away from the bracketing critical point
+                let dif = if open_min { xn - xr } else { xr - xn };
+                if dif <= T::ZERO && xn.is_finite() {
                     xr = xn;
-                    if dif <= tol {
+                    if -dif <= tol {
                         if xr == xm {
                             break 'main_loop;
                         };
-                        let xs = xn - tol * if openMin { -1. } else { 1. };
+                        let xs = if open_min { xn - tol } else { xn + tol };
                         let ys = p(xs);
-                        let s = (ym < 0.) != (ys < 0.);
-                        if s {
+                        if is_different_sign(ym, ys) {
                             break 'main_loop;
                         };
                         xr = xs;
@@ -206,11 +244,11 @@ fn find_open_helper(
                         continue 'open_interval;
                     }
                 } else {
-                    xr = if openMin { xr - delta } else { xr + delta };
-                    delta += 2.;
+                    xr = if open_min { xr - delta } else { xr + delta };
+                    delta += delta;
                 }
                 yr = p(xr);
-                otherside = (ym < 0.) != (yr < 0.);
+                otherside = is_different_sign(ym, yr);
                 continue 'main_loop;
             }
         }
@@ -219,118 +257,581 @@ fn find_open_helper(
     xr
 }
 
+/// Bounds the rounding error of evaluating the polynomial c at x by Horner's
+/// rule.
 #[inline]
-pub fn roots_quadratic(p: &Polynomial<f64, 3>) -> [f64; 2] {
-    let mut output = [f64::NAN; 2];
-    let a = p.c[0];
-    let b = p.c[1];
-    let c = p.c[2];
-    let delta = b * b - 4. * a * c;
-    if delta > 0. {
-        // Two real roots
+fn rounding<T: Float>(c: &[T], x: T) -> T {
+    let magnitude = c.iter().fold(T::ZERO, |acc, &k| acc * x.abs() + k.abs());
+    T::from_usize(2 * c.len()) * T::EPSILON * magnitude
+}
+
+/// The successive derivatives of a polynomial of degree d, in the rows of a
+/// table stride apart, row k holding the kth with d - k + 1 coefficients.
+#[derive(Copy, Clone)]
+struct Derivatives<'a, T: Float> {
+    table: &'a [T],
+    stride: usize,
+    d: usize,
+}
+
+impl<'a, T: Float> Derivatives<'a, T> {
+    #[inline]
+    fn row(&self, k: usize) -> &'a [T] {
+        &self.table[k * self.stride..k * self.stride + self.d - k + 1]
+    }
+
+    /// Whether the kth derivative vanishes at x, a root of the (k+1)th found
+    /// to within tol. It does if its value there is within the rounding error
+    /// of evaluating it plus the most its Taylor expansion lets it change over
+    /// a distance of tol, in which case x is a root of both.
+    fn vanishes(&self, k: usize, x: T, tol: T) -> bool {
+        let c = self.row(k);
+        let mut bound = rounding(c, x);
+        let mut power = T::ONE;
+        for j in 1..=self.d - k {
+            power = power * tol / T::from_usize(j);
+            bound += horner(self.row(k + j), x).abs() * power;
+        }
+        horner(c, x).abs() <= bound
+    }
+
+    /// Bounds the distance of x from a simple root of the polynomial by
+    /// Kantorovich's theorem. With β the Newton step |p(x) / p'(x)|, allowing
+    /// for rounding, and L a bound on |p''| within 2β of x, there is a root
+    /// within 2β if β L ≤ |p'(x)| / 2. Returns None where that fails.
+    fn error(&self, x: T) -> Option<T> {
+        let two = T::ONE + T::ONE;
+        let (p, dp) = (self.row(0), self.row(1));
+        let y = horner(p, x).abs() + rounding(p, x);
+        let dy = horner(dp, x).abs() - rounding(dp, x);
+        if dy <= T::ZERO {
+            return None;
+        }
+        let r = two * y / dy;
+
+        // |p''| within r of x, by its Taylor expansion about x
+        let mut l = T::ZERO;
+        let mut power = T::ONE;
+        for j in 2..=self.d {
+            let c = self.row(j);
+            l += (horner(c, x).abs() + rounding(c, x)) * power;
+            power = power * r / T::from_usize(j - 1);
+        }
+        if r * l <= dy && r.is_finite() {
+            Some(r)
+        } else {
+            None
+        }
+    }
+}
+
+/// Writes the distinct real roots of the quadratic kth derivative in
+/// ascending order, with their multiplicities, returning their number. The
+/// root xv of the (k+1)th is its vertex, which is a double root if the kth
+/// vanishes there.
+#[inline]
+fn quadratic<T: Float>(
+    f: &Derivatives<T>,
+    k: usize,
+    xv: T,
+    output: &mut [(T, usize)],
+    tol: T,
+) -> usize {
+    if f.vanishes(k, xv, tol) {
+        output[0] = (xv, 2);
+        return 1;
+    }
+    let two = T::ONE + T::ONE;
+    let [a, b, c] = [f.row(k)[0], f.row(k)[1], f.row(k)[2]];
+    let delta = b * b - two * two * a * c;
+    if delta > T::ZERO {
         let d = delta.sqrt();
-        let q = -0.5 * (b + d.copysign(b.signum()));
+        let q = -(b + d.copysign(b)) / two;
         let rv0 = q / a;
         let rv1 = c / q;
-        output[0] = rv0.min(rv1);
-        output[1] = rv0.max(rv1);
-        return output;
-    } else if delta < 0. { // Roots are complex conjugate pair, return NaNs
+        if rv0 < rv1 {
+            [output[0], output[1]] = [(rv0, 1), (rv1, 1)];
+        } else {
+            [output[0], output[1]] = [(rv1, 1), (rv0, 1)];
+        }
+        2
     } else {
-        // One real root
-        output[0] = -0.5 * b / a;
+        0
     }
-
-    output
 }
 
-#[inline]
-pub fn roots_cubic(f: &Polynomial<f64, 4>, tol: f64) -> [f64; 3] {
-    let mut output = [f64::NAN; 3];
-    let a = f.c[0] * 3.;
-    let b_2 = f.c[1];
-    let c = f.c[2];
+/// Finds the real roots of the kth derivative from the sorted real roots dr
+/// of the (k+1)th, which split the real line into monotonic intervals holding
+/// at most one root each. A root of the (k+1)th at which the kth vanishes is
+/// a root of the kth of one greater multiplicity, and the intervals either
+/// side of it hold none. Writes the roots in ascending order, with their
+/// multiplicities, and returns their number.
+fn expand<T: Float>(
+    f: &Derivatives<T>,
+    k: usize,
+    dr: &[(T, usize)],
+    output: &mut [(T, usize)],
+    tol: T,
+) -> usize {
+    let (c, dc) = (f.row(k), f.row(k + 1));
+    let degree = c.len() - 1;
+    let odd = (degree & 1) != 0;
+    let p = |x| horner(c, x);
+    let dp = |x| horner(dc, x);
 
-    let df = Polynomial::<f64, 3>::from([a, 2. * b_2, c]);
-    let p = { |x| f.eval(x) };
-    let dp = { |x| df.eval(x) };
+    let mut nr = 0;
+    if dr.is_empty() {
+        if odd {
+            output[nr] = (find_open(degree, c, dp, tol), 1);
+            nr += 1;
+        }
+        return nr;
+    }
 
-    let delta_4 = b_2 * b_2 - a * c;
+    // The last critical point, its value, and whether it is a root
+    let mut last: Option<(T, T, bool)> = None;
+    for &(xb, m) in dr {
+        let yb = p(xb);
+        let tb = f.vanishes(k, xb, tol);
+        match last {
+            None if !tb && is_different_sign(yb, c[0]) != odd => {
+                output[nr] = (find_open_min(degree, p, dp, xb, yb, tol), 1);
+                nr += 1;
+            }
+            Some((xa, ya, ta)) if !ta && !tb && is_different_sign(ya, yb) => {
+                output[nr] = (find_closed(degree, p, dp, xa, xb, ya, tol), 1);
+                nr += 1;
+            }
+            _ => {}
+        }
+        if tb {
+            output[nr] = (xb, m + 1);
+            nr += 1;
+        }
+        last = Some((xb, yb, tb));
+    }
+    if let Some((xa, ya, false)) = last {
+        if is_different_sign(ya, c[0]) {
+            output[nr] = (find_open_max(degree, p, dp, xa, ya, tol), 1);
+            nr += 1;
+        }
+    }
+    nr
+}
 
-    if delta_4 > 0. {
-        let d_2 = delta_4.sqrt();
-        let q = -(b_2 + (d_2 * if b_2.signum() < 0. { -1. } else { 1. }));
-        let rv0 = q / a;
-        let rv1 = c / q;
-        let xa = rv0.min(rv1);
-        let xb = rv0.max(rv1);
+/// Finds the real roots of the kth derivative in [x_min, x_max] from the
+/// sorted real roots dr of the (k+1)th, as expand does, skipping those outside
+/// the interval. Its ends close the first and last monotonic pieces. An end at
+/// which the derivative is exactly zero is a root, taking the multiplicity of
+/// a root of dr there plus one, and bounds no bracket, as its sign is neither.
+fn expand_in<T: Float>(
+    f: &Derivatives<T>,
+    k: usize,
+    dr: &[(T, usize)],
+    (x_min, x_max): (T, T),
+    output: &mut [(T, usize)],
+    tol: T,
+) -> usize {
+    let (c, dc) = (f.row(k), f.row(k + 1));
+    let degree = c.len() - 1;
+    let p = |x| horner(c, x);
+    let dp = |x| horner(dc, x);
 
-        let ya = p(xa);
-        let yb = p(xb);
+    let at_end = |x| 1 + dr.iter().find(|&&(r, _)| r == x).map_or(0, |&(_, m)| m);
 
-        if a.signum() == ya.signum() {
-            output[0] = find_open_min(3, p, dp, xa, ya, tol);
-            if (ya < 0.) != (yb < 0.) {
-                output[1] = find_closed(3, p, dp, xa, xb, 0., 0., tol);
-                output[2] = find_open_max(3, p, dp, xb, yb, tol);
-            }
+    let mut nr = 0;
+    let (mut xa, mut ya) = (x_min, p(x_min));
+    let mut ta = ya == T::ZERO;
+    if ta {
+        output[nr] = (x_min, at_end(x_min));
+        nr += 1;
+    }
+    let inside = dr.iter().copied().filter(|&(x, _)| x > x_min && x < x_max);
+    for (xb, m) in inside.chain(core::iter::once((x_max, 0))) {
+        let yb = p(xb);
+        let tb = if m > 0 {
+            f.vanishes(k, xb, tol)
         } else {
-            output[0] = find_open_max(3, p, dp, xb, yb, tol);
+            yb == T::ZERO && xb > x_min
+        };
+        if !ta && !tb && is_different_sign(ya, yb) {
+            output[nr] = (find_closed(degree, p, dp, xa, xb, ya, tol), 1);
+            nr += 1;
+        }
+        if tb {
+            output[nr] = (xb, if m > 0 { m + 1 } else { at_end(xb) });
+            nr += 1;
+        }
+        (xa, ya, ta) = (xb, yb, tb);
+    }
+    nr
+}
+
+/// Finds the real roots of the polynomial c, of degree d = c.len() - 1 with a
+/// nonzero leading coefficient, over the whole real line or in
+/// [x_min, x_max] where bounds are given. The caller provides the scratch:
+/// a table of d + 1 rows, stride apart with a stride of at least d + 1, which
+/// is left holding the derivatives, and roots and next of at least d entries
+/// each. Writes the distinct roots in ascending order, with their
+/// multiplicities, to the start of roots and returns their number.
+#[allow(clippy::too_many_arguments)]
+fn solve_in<T: Float>(
+    c: &[T],
+    bounds: Option<(T, T)>,
+    tol: T,
+    table: &mut [T],
+    stride: usize,
+    roots: &mut [(T, usize)],
+    next: &mut [(T, usize)],
+) -> usize {
+    let d = c.len() - 1;
+
+    // Row k of the table holds the kth derivative, with d - k + 1 coefficients
+    table[..=d].copy_from_slice(c);
+    for k in 1..=d {
+        let (prev, row) = table.split_at_mut(k * stride);
+        let prev = &prev[(k - 1) * stride..];
+        for (i, e) in row.iter_mut().enumerate().take(d - k + 1) {
+            *e = prev[i] * T::from_usize(d - k + 1 - i);
         }
+    }
+    let f = Derivatives { table, stride, d };
+
+    let mut n = if d == 1 {
+        roots[0] = (-c[1] / c[0], 1);
+        1
     } else {
-        let x_inf = -b_2 / a;
-        let y_inf = p(x_inf);
-        if a.signum() != y_inf.signum() {
-            output[0] = find_open_max(3, p, dp, x_inf, y_inf, tol);
-        } else {
-            output[0] = find_open_min(3, p, dp, x_inf, y_inf, tol);
+        let linear = f.row(d - 1);
+        quadratic(&f, d - 2, -linear[1] / linear[0], roots, tol)
+    };
+    let (mut current, mut other) = (&mut *roots, &mut *next);
+    let mut in_next = false;
+    for k in (0..d.saturating_sub(2)).rev() {
+        n = match bounds {
+            Some(bounds) => expand_in(&f, k, &current[..n], bounds, other, tol),
+            None => expand(&f, k, &current[..n], other, tol),
+        };
+        core::mem::swap(&mut current, &mut other);
+        in_next = !in_next;
+    }
+    if in_next {
+        other[..n].copy_from_slice(&current[..n]);
+    }
+    n
+}
+
+/// Finds the real roots over the whole real line, or in [x_min, x_max] where
+/// bounds are given.
+fn solve<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    bounds: Option<(T, T)>,
+    tol: T,
+) -> RootSet<T, { N - 1 }> {
+    let mut output = RootSet::new();
+    let s = p.c.iter().position(|&k| k != T::ZERO).unwrap_or(N);
+    if s + 1 >= N {
+        return output;
+    }
+    let d = N - 1 - s;
+
+    let mut table = [[T::ZERO; N]; N];
+    let mut roots = [(T::ZERO, 0); N];
+    let mut next = [(T::ZERO, 0); N];
+    let n = solve_in(
+        &p.c[s..],
+        bounds,
+        tol,
+        table.as_flattened_mut(),
+        N,
+        &mut roots,
+        &mut next,
+    );
+
+    let f = Derivatives {
+        table: table.as_flattened(),
+        stride: N,
+        d,
+    };
+    let inside = |x: T| match bounds {
+        Some((x_min, x_max)) => x >= x_min && x <= x_max,
+        None => true,
+    };
+    for &(x, m) in roots[..n].iter().filter(|&&(x, _)| inside(x)) {
+        output.insert(x, m, if m == 1 { f.error(x) } else { None });
+    }
+    output
+}
+
+/// Finds the real roots of a polynomial of any degree.
+///
+/// The roots of each derivative bound the intervals in which the next lower
+/// derivative is monotonic, so starting from the closed-form roots of the
+/// quadratic (N-3)th derivative each derivative's roots are found in turn up
+/// to the polynomial itself. Roots are found to within tol. A root of a
+/// derivative at which the polynomial vanishes, within its rounding error and
+/// the change tol allows, is a multiple root, and is returned once with its
+/// multiplicity. Simple roots carry an error bound by Kantorovich's theorem
+/// where it holds, and multiple roots none. Leading zero coefficients are
+/// ignored.
+pub fn roots<T: Float, const N: usize>(p: &Polynomial<T, N>, tol: T) -> RootSet<T, { N - 1 }> {
+    solve(p, None, tol)
+}
+
+/// Finds the real roots of a polynomial of any degree in [x_min, x_max].
+///
+/// As in Yuksel's original method, only the roots of each derivative inside
+/// the interval are found, with its ends closing the first and last monotonic
+/// pieces. This skips the open-ended searches for roots outside it, so is
+/// faster than filtering the result of roots. Roots on the ends are included,
+/// as for a ray or collision query starting at a root.
+pub fn roots_in<T: Float, const N: usize>(
+    p: &Polynomial<T, N>,
+    x_min: T,
+    x_max: T,
+    tol: T,
+) -> RootSet<T, { N - 1 }> {
+    solve(p, Some((x_min, x_max)), tol)
+}
+
+/// Finds the real roots of a polynomial whose degree is known only at
+/// runtime, as roots does, given its coefficients c with a nonzero leading
+/// one. Returns the distinct roots in ascending order, with their
+/// multiplicities and error bounds.
+#[cfg(feature = "alloc")]
+pub(crate) fn roots_dyn<T: Float>(c: &[T], tol: T) -> Vec<(T, usize, Option<T>)> {
+    let d = c.len().saturating_sub(1);
+    if d == 0 {
+        return Vec::new();
+    }
+    let mut table = vec![T::ZERO; (d + 1) * (d + 1)];
+    let mut roots = vec![(T::ZERO, 0); d];
+    let mut next = vec![(T::ZERO, 0); d];
+    let n = solve_in(c, None, tol, &mut table, d + 1, &mut roots, &mut next);
+    let f = Derivatives {
+        table: &table,
+        stride: d + 1,
+        d,
+    };
+    roots[..n]
+        .iter()
+        .map(|&(x, m)| (x, m, if m == 1 { f.error(x) } else { None }))
+        .collect()
+}
+
+/// Finds the real roots of a quadratic in closed form, falling back to the
+/// linear root should the leading coefficient vanish. A nonzero constant has
+/// no roots, and the zero polynomial fails, as every x is a root.
+#[inline]
+pub fn roots_quadratic<T: Float>(p: &Polynomial<T, 3>) -> RootSet<T, 2> {
+    let mut output = RootSet::new();
+    let two = T::ONE + T::ONE;
+    let a = p.c[0];
+    let b = p.c[1];
+    let c = p.c[2];
+    if a == T::ZERO {
+        if b != T::ZERO {
+            output.push(-c / b);
+        } else if c == T::ZERO {
+            output.fail();
         }
+        return output;
+    }
+    let delta = b * b - two * two * a * c;
+    if delta > T::ZERO {
+        // Two real roots
+        let d = delta.sqrt();
+        let q = -(b + d.copysign(b)) / two;
+        output.push(q / a);
+        output.push(c / q);
+    } else if delta == T::ZERO {
+        // One real root of multiplicity two
+        output.insert(-b / (two * a), 2, None);
     }
+    // Otherwise the roots are a complex conjugate pair
 
     output
 }
 
 #[inline]
-pub fn roots_quartic(f: &Polynomial<f64, 5>, tol: f64) -> [f64; 4] {
-    const N: usize = 4;
-    let mut output = [f64::NAN; N];
-    let df = Polynomial::<f64, 4>::from([4. * f.c[0], 3. * f.c[1], 2. * f.c[2], f.c[3]]);
-    let derivRoots = roots_cubic(&df, tol);
-
-    let p = { |x| f.eval(x) };
-    let dp = { |x| df.eval(x) };
-    let nd = derivRoots
-        .iter()
-        .map(|x| if x.is_finite() { 1 } else { 0 })
-        .sum::<usize>();
-    if ((N & 1) != 0) || ((N & 1) == 0 && nd > 0) {
-        let mut nr = 0;
-        let mut xa = derivRoots[0];
-        let mut ya = p(xa);
-        if ((ya < 0.) != (f.c[0] < 0.)) != ((N & 1) != 0) {
-            output[0] = find_open_min(N, p, dp, xa, ya, tol);
-            nr = 1;
+pub fn roots_cubic<T: Float>(f: &Polynomial<T, 4>, tol: T) -> RootSet<T, 3> {
+    roots(f, tol)
+}
+
+#[inline]
+pub fn roots_quartic<T: Float>(f: &Polynomial<T, 5>, tol: T) -> RootSet<T, 4> {
+    roots(f, tol)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roots_4() {
+        let tol = 1e-12;
+
+        // (x + 3)(x + 1)(x - 2)(x - 5) = x^4 - 3x^3 - 15x^2 + 19x + 30
+        let p = Polynomial::new([1., -3., -15., 19., 30.]);
+        let r = roots_quartic(&p, tol);
+        for (i, b) in [-3., -1., 2., 5.].into_iter().enumerate() {
+            assert!((r[i] - b).abs() < tol);
+            assert!((r[i] - b).abs() <= r.error(i).unwrap());
         }
-        for i in 1..nd {
-            let xb = derivRoots[i];
-            let yb = p(xb);
-            if (ya < 0.) != (yb < 0.) {
-                output[nr] = find_closed(N, p, dp, xa, xb, ya, yb, tol);
-                nr += 1;
-            }
-            xa = xb;
-            ya = yb;
+
+        // (x^2 + 1)(x - 4)(x + 0.5), with only two real roots
+        let p = Polynomial::new([1., 0., 1.]) * Polynomial::new([1., -3.5, -2.]);
+        let r = roots_quartic(&p, tol);
+        assert!((r[0] + 0.5).abs() < tol);
+        assert!((r[1] - 4.).abs() < tol);
+        assert_eq!(r.len(), 2);
+        assert!(r.error(0).unwrap() < tol);
+
+        // x^4 + 1 has no real roots
+        let r = roots_quartic(&Polynomial::new([1., 0., 0., 0., 1.]), tol);
+        assert!(r.is_empty() && !r.failed());
+    }
+
+    #[test]
+    fn roots_odd_monotonic() {
+        // x^3 + x + 10 has a single real root at -2 and no critical points
+        let r = roots_cubic(&Polynomial::new([1., 0., 1., 10.]), 1e-12);
+        assert!((r[0] + 2.).abs() < 1e-12);
+        assert_eq!(r.len(), 1);
+
+        // x^5 + x - 34 has a single real root at 2
+        let r = roots(&Polynomial::new([1., 0., 0., 0., 1., -34.]), 1e-12);
+        assert!((r[0] - 2.).abs() < 1e-12);
+        assert_eq!(r.len(), 1);
+    }
+
+    #[test]
+    fn roots_6_f32() {
+        let tol = 1e-4;
+
+        // (x + 2)(x + 1)(x - 0.5)(x - 1)(x - 3)(x - 4)
+        let p = Polynomial::new([1.0_f32, 2.0])
+            * Polynomial::new([1.0, 1.0])
+            * Polynomial::new([1.0, -0.5])
+            * Polynomial::new([1.0, -1.0])
+            * Polynomial::new([1.0, -3.0])
+            * Polynomial::new([1.0, -4.0]);
+        let r = roots(&p, tol);
+        assert_eq!(r.len(), 6);
+        for (a, b) in r.iter().zip([-2.0, -1.0, 0.5, 1.0, 3.0, 4.0]) {
+            assert!((a - b).abs() < 1e-3);
         }
-        if (ya < 0.) != (f.c[0] < 0.) {
-            output[nr] = find_open_max(N, p, dp, xa, ya, tol);
-            // nr += 1;
+    }
+
+    #[test]
+    fn roots_bounded() {
+        let tol = 1e-12;
+
+        // (x + 3)(x + 1)(x - 2)(x - 5)
+        let p = Polynomial::new([1., -3., -15., 19., 30.]);
+        let r = roots_in(&p, 0., 4., tol);
+        assert_eq!(r.len(), 1);
+        assert!((r[0] - 2.).abs() < tol);
+        let r = roots_in(&p, -2., 10., tol);
+        assert_eq!(r.len(), 3);
+        for (a, b) in r.iter().zip([-1., 2., 5.]) {
+            assert!((a - b).abs() < tol);
         }
-    } else {
-        if (N & 1) != 0 {
-            // output[0] = find_open(N, p, dp, tol);
-            todo!()
+        assert!(roots_in(&p, 2.5, 4.5, tol).is_empty());
+
+        // (x + 2)(x + 1)(x - 0.5)(x - 1)(x - 3)(x - 4), over [0, t_max]
+        let p = Polynomial::new([1., 2.])
+            * Polynomial::new([1., 1.])
+            * Polynomial::new([1., -0.5])
+            * Polynomial::new([1., -1.])
+            * Polynomial::new([1., -3.])
+            * Polynomial::new([1., -4.]);
+        let r = roots_in(&p, 0., 3.5, tol);
+        assert_eq!(r.len(), 3);
+        for (a, b) in r.iter().zip([0.5, 1., 3.]) {
+            assert!((a - b).abs() < tol);
         }
+
+        // Roots on either end are kept, whatever the sign at the other
+        let p = Polynomial::new([1., -6., 11., -6.]);
+        assert_eq!(roots_in(&p, 1., 1.5, tol), [1.]);
+        assert_eq!(roots_in(&p, 3., 4., tol), [3.]);
+        assert_eq!(roots_in(&p, 0., 1., tol), [1.]);
+        assert_eq!(roots_in(&p, 2.5, 3., tol), [3.]);
+        assert_eq!(roots_in(&p, 1., 3., tol).len(), 3);
+        assert_eq!(roots_in(&p, 2., 2., tol), [2.]);
+        let p = Polynomial::new([1., -15., 85., -225., 274., -120.]);
+        assert_eq!(roots_in(&p, 1., 1.5, tol), [1.]);
+        assert_eq!(roots_in(&p, 5., 6., tol), [5.]);
+
+        // (x - 1)^2 (x - 3) keeps its double root at the start
+        let r = roots_in(&Polynomial::new([1., -5., 7., -3.]), 1., 2., tol);
+        assert_eq!(r, [1.]);
+        assert_eq!(r.multiplicity(0), 2);
+
+        // Closed-form quadratic and linear roots are filtered to the interval
+        let r = roots_in(&Polynomial::new([1., -1., -12.]), 0., 10., tol);
+        assert_eq!(r, [4.]);
+        let r = roots_in(&Polynomial::new([2., -1.]), 1., 2., tol);
+        assert!(r.is_empty());
     }
 
-    output
+    #[test]
+    fn roots_multiple() {
+        let tol = 1e-12;
+
+        // (x - 1)^2 (x + 1)(x + 2), whose double root has no sign change
+        let r = Polynomial::new([1., 1., -3., -1., 2.]).roots(tol);
+        assert_eq!(r.len(), 3);
+        assert!(!r.failed());
+        for (i, (b, m)) in [(-2., 1), (-1., 1), (1., 2)].into_iter().enumerate() {
+            assert!((r[i] - b).abs() < tol);
+            assert_eq!(r.multiplicity(i), m);
+        }
+        assert!((r[1] + 1.).abs() <= r.error(1).unwrap());
+        assert_eq!(r.error(2), None);
+
+        // (x - 1)^2 (x - 3), whose double root rounding splits in two
+        let r = Polynomial::new([1., -5., 7., -3.]).roots(tol);
+        assert_eq!(r.len(), 2);
+        assert!((r[0] - 1.).abs() < tol && (r[1] - 3.).abs() < tol);
+        assert_eq!(r.multiplicity(0), 2);
+        assert_eq!(r.count(), 3);
+
+        // (x - 1)^4 (x - 3), through a chain of vanishing derivatives
+        let r = Polynomial::new([1., -7., 18., -22., 13., -3.]).roots(tol);
+        assert_eq!(r.len(), 2);
+        assert_eq!(r.multiplicity(0), 4);
+
+        // Roots 1e-5 apart are distinct for a smaller tol
+        let p = Polynomial::new([1., -1.]) * Polynomial::new([1., -1.00001]);
+        let r = (p * Polynomial::new([1., 5.])).roots(tol);
+        assert_eq!(r.len(), 3);
+        assert!((r[2] - r[1] - 1e-5).abs() < 1e-9);
+        assert!(r.error(1).unwrap() < 1e-8);
+    }
+
+    #[test]
+    fn roots_degree_drop() {
+        let tol = 1e-12;
+
+        // 0x^4 + 0x^3 + x^2 - 1
+        let r = roots(&Polynomial::new([0., 0., 1., 0., -1.]), tol);
+        assert_eq!(r[0], -1.);
+        assert_eq!(r[1], 1.);
+        assert_eq!(r.len(), 2);
+        assert!(r.error(0).unwrap() < 1e-14);
+
+        // Linear and constant polynomials
+        let r = roots(&Polynomial::new([2., -1.]), tol);
+        assert_eq!(r, [0.5]);
+        let r = roots(&Polynomial::new([0., 0., 3.]), tol);
+        assert!(r.is_empty());
+
+        // A nonzero constant has no roots, while the zero polynomial fails
+        let r = roots_quadratic(&Polynomial::new([0., 0., 3.]));
+        assert!(r.is_empty() && !r.failed());
+        let r = roots_quadratic(&Polynomial::new([0., 0., 0.]));
+        assert!(r.is_empty() && r.failed());
+        assert_eq!(roots_quadratic(&Polynomial::new([0., 2., -1.])), [0.5]);
+    }
 }